@@ -0,0 +1,68 @@
+//! Compare the adjacency list `Graph` with the compressed sparse row `CsrGraph`
+//!
+//! cargo bench --bench csr -- [num_edges]
+//!
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+use flex_algo::{CsrGraph, Dijkstra, Graph};
+
+// the best time of a few runs
+fn measure<T, F: FnMut() -> T>(runs: usize, mut f: F) -> Duration {
+    (0..runs)
+        .map(|_| {
+            let start = Instant::now();
+            black_box(f());
+            start.elapsed()
+        })
+        .min()
+        .unwrap()
+}
+
+fn report(name: &str, graph: Duration, csr: Duration) {
+    println!(
+        "{:<20} {:>12.2?} {:>12.2?} {:>8.2}x",
+        name,
+        graph,
+        csr,
+        graph.as_secs_f64() / csr.as_secs_f64()
+    );
+}
+
+fn main() {
+    let num_edges: usize = std::env::args().skip(1).find_map(|arg| arg.parse().ok()).unwrap_or(2_000_000);
+    // at least one node, so that the random endpoints of a small input stay in range
+    let num_nodes = (num_edges / 8).max(1);
+    let runs = 3;
+
+    // a pseudo random graph, and a dag from the smaller to the larger node
+    let mut seed: u64 = 42;
+    let mut next = || {
+        seed = seed.wrapping_mul(6_364_136_223_846_793_005).wrapping_add(1_442_695_040_888_963_407);
+        (seed >> 33) as usize
+    };
+    let edges: Vec<(usize, usize, u32)> = (0..num_edges).map(|_| (next() % num_nodes, next() % num_nodes, (next() % 100) as u32)).collect();
+    let dag: Vec<(usize, usize, ())> = edges
+        .iter()
+        .filter(|&&(source, target, _)| source < target)
+        .map(|&(source, target, _)| (source, target, ()))
+        .collect();
+    println!("{} nodes, {} edges, best of {} runs", num_nodes, num_edges, runs);
+    println!("{:<20} {:>12} {:>12} {:>9}", "", "Graph", "CsrGraph", "speedup");
+
+    let build_graph = measure(runs, || Graph::from_edges(vec![(); num_nodes], edges.clone()));
+    let build_csr = measure(runs, || CsrGraph::from_edges(num_nodes, edges.clone()));
+    report("build", build_graph, build_csr);
+
+    let graph = Graph::from_edges(vec![(); num_nodes], edges.clone());
+    let csr = CsrGraph::from(&graph);
+    report("bfs", measure(runs, || graph.bfs(0)), measure(runs, || csr.bfs(0)));
+    report("dfs", measure(runs, || graph.dfs_all()), measure(runs, || csr.dfs_all()));
+
+    let graph = Graph::from_edges(vec![(); num_nodes], dag.clone());
+    let csr = CsrGraph::from_edges(num_nodes, dag);
+    report("topological sort", measure(runs, || graph.topological_sort()), measure(runs, || csr.topological_sort()));
+
+    let dijkstra = Dijkstra::new(num_nodes, edges);
+    let csr = CsrGraph::from(&dijkstra);
+    report("dijkstra", measure(runs, || dijkstra.shortest_paths(0)), measure(runs, || csr.shortest_paths(0)));
+}
@@ -0,0 +1,383 @@
+use std::fmt::Debug;
+use crate::bellman_ford::{BellmanFord, NegativeCycle};
+use crate::dijkstra::Dijkstra;
+use crate::weight::Weight;
+
+/// All pairs shortest paths
+///
+/// This crate implements the Floyd-Warshall algorithm for dense graphs and the Johnson algorithm
+/// for sparse graphs, the result is a distance matrix with a next hop matrix to rebuild the paths.
+///
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AllPairs<W = usize> {
+    distances: Vec<Vec<Option<W>>>,
+    next_hops: Vec<Vec<Option<usize>>>,
+}
+
+impl<W: Weight> AllPairs<W> {
+    /// Compute the shortest paths between all the nodes by the Floyd-Warshall algorithm in O(n^3)
+    /// with edges tuple(current, neighbor, weight) Vec, or return a negative cycle
+    ///
+    /// # Panics
+    ///
+    /// Panics if a weight is NaN or if a path doesn't even fit the wide type of `W` without a negative cycle,
+    /// which only happens to the weights that are their own wide type.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flex_algo::AllPairs;
+    ///
+    /// let edges = vec![(0, 1, 3), (1, 2, -2), (0, 2, 4), (2, 0, 1)];
+    /// let all_pairs = AllPairs::floyd_warshall(3, edges).unwrap();
+    /// assert_eq!(all_pairs.distance(0, 2), Some(1));
+    /// assert_eq!(all_pairs.path(1, 0), Some((-1, vec![1, 2, 0])));
+    ///
+    /// ```
+    pub fn floyd_warshall(num_nodes: usize, edges: Vec<(usize, usize, W)>) -> Result<Self, NegativeCycle> {
+        // the distances are kept in the wide type and narrowed once they are final
+        let mut distances = vec![vec![None; num_nodes]; num_nodes];
+        let mut next_hops = vec![vec![None; num_nodes]; num_nodes];
+        for node in 0..num_nodes {
+            distances[node][node] = Some(W::zero().widen());
+            next_hops[node][node] = Some(node);
+        }
+        for &(source, target, weight) in &edges {
+            assert!(weight.is_valid(), "invalid edge weight: {:?}", weight);
+            let weight = weight.widen();
+            if distances[source][target].is_none_or(|current| weight < current) {
+                distances[source][target] = Some(weight);
+                next_hops[source][target] = Some(target);
+            }
+        }
+
+        let mut underflow = false;
+        for k in 0..num_nodes {
+            for i in 0..num_nodes {
+                let to_k = match distances[i][k] {
+                    Some(distance) => distance,
+                    None => continue,
+                };
+                for j in 0..num_nodes {
+                    let from_k = match distances[k][j] {
+                        Some(distance) => distance,
+                        None => continue,
+                    };
+                    let distance = match to_k.checked_add(from_k) {
+                        Some(distance) => distance,
+                        // a distance below the minimum of the wide type is either on a negative cycle or doesn't fit it
+                        None => {
+                            underflow |= from_k < W::zero().widen();
+                            continue;
+                        }
+                    };
+                    if distances[i][j].is_none_or(|current| distance < current) {
+                        distances[i][j] = Some(distance);
+                        next_hops[i][j] = next_hops[i][k];
+                    }
+                }
+            }
+        }
+
+        // a node with a negative distance to itself is on a negative cycle, Bellman-Ford gives the cycle back
+        if underflow || (0..num_nodes).any(|node| distances[node][node].unwrap() < W::zero().widen()) {
+            if let Some(cycle) = BellmanFord::new(num_nodes, edges).negative_cycle() {
+                return Err(cycle);
+            }
+            panic!("distance overflow");
+        }
+        // the next hops of a distance that doesn't fit W are kept for the paths going through it
+        let distances = distances
+            .into_iter()
+            .map(|row| row.into_iter().map(|distance| W::narrow(distance?)).collect())
+            .collect();
+        Ok(AllPairs {
+            distances,
+            next_hops,
+        })
+    }
+
+    /// Compute the shortest paths between all the nodes by the Johnson algorithm in O(nm log n)
+    /// with edges tuple(current, neighbor, weight) Vec, or return a negative cycle
+    ///
+    /// The weights are made non negative by the potentials of Bellman-Ford,
+    /// then Dijkstra is run from every node.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a weight is NaN, if a path doesn't even fit the wide type of `W` without a negative cycle
+    /// or if a reweighted weight `w + h(source) - h(target)` overflows the wide type,
+    /// which only happens to the weights that are their own wide type.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flex_algo::AllPairs;
+    ///
+    /// let edges = vec![(0, 1, 3), (1, 2, -2), (0, 2, 4), (2, 0, 1)];
+    /// let all_pairs = AllPairs::johnson(3, edges).unwrap();
+    /// assert_eq!(all_pairs.distance(0, 2), Some(1));
+    /// assert_eq!(all_pairs.path(1, 0), Some((-1, vec![1, 2, 0])));
+    ///
+    /// ```
+    pub fn johnson(num_nodes: usize, edges: Vec<(usize, usize, W)>) -> Result<Self, NegativeCycle> {
+        // the potentials, the reweighted weights and the distances are all in the wide type
+        let potentials = BellmanFord::new(num_nodes, edges.clone()).potentials()?;
+        let zero = W::zero().widen();
+        let reweighted = edges
+            .into_iter()
+            .map(|(source, target, weight)| {
+                // the potentials are not positive, w - h(target) only grows and w + h(source) only shrinks,
+                // a weight that is its own wide type may overflow in one order while the reweighted weight fits
+                let weight = weight
+                    .widen()
+                    .checked_sub(potentials[target])
+                    .and_then(|weight| weight.checked_add(potentials[source]))
+                    .or_else(|| weight.widen().checked_add(potentials[source])?.checked_sub(potentials[target]))
+                    .expect("reweighted edge weight overflow");
+                // float rounding may leave a tiny negative weight
+                if weight < zero {
+                    return (source, target, zero);
+                }
+                (source, target, weight)
+            })
+            .collect();
+        let dijkstra = Dijkstra::new(num_nodes, reweighted);
+
+        let mut distances = vec![vec![None; num_nodes]; num_nodes];
+        let mut next_hops = vec![vec![None; num_nodes]; num_nodes];
+        for source in 0..num_nodes {
+            let paths = dijkstra.shortest_paths(source);
+            let predecessors = paths.predecessors();
+            next_hops[source][source] = Some(source);
+            for target in 0..num_nodes {
+                let Some(distance) = paths.distance(target) else {
+                    continue;
+                };
+                // d + h(target) stays between h(target) and d, only the final distance may not fit W,
+                // its next hop is still kept for the paths going through the target
+                distances[source][target] = distance
+                    .checked_add(potentials[target])
+                    .and_then(|distance| distance.checked_sub(potentials[source]))
+                    .and_then(W::narrow);
+                if next_hops[source][target].is_some() {
+                    continue;
+                }
+                // walk back the predecessors up to the first hop or a node whose next hop is known
+                let mut stack = vec![target];
+                let mut vertex = target;
+                let next_hop = loop {
+                    let predecessor = predecessors[vertex].unwrap();
+                    if predecessor == source {
+                        break vertex;
+                    }
+                    if let Some(next_hop) = next_hops[source][predecessor] {
+                        break next_hop;
+                    }
+                    stack.push(predecessor);
+                    vertex = predecessor;
+                };
+                for node in stack {
+                    next_hops[source][node] = Some(next_hop);
+                }
+            }
+        }
+        Ok(AllPairs {
+            distances,
+            next_hops,
+        })
+    }
+
+    /// Return the distance matrix, `None` when the target is unreachable from the source
+    /// or only reached through paths whose distance overflows `W`
+    pub fn distances(&self) -> &[Vec<Option<W>>] {
+        &self.distances
+    }
+
+    /// Return the next hop matrix, the node following the source on its shortest path to the target
+    pub fn next_hops(&self) -> &[Vec<Option<usize>>] {
+        &self.next_hops
+    }
+
+    /// Return the distance from the source to the target,
+    /// or `None` if the target is unreachable or its distance overflows `W`
+    pub fn distance(&self, source: usize, target: usize) -> Option<W> {
+        self.distances.get(source)?.get(target).cloned().flatten()
+    }
+
+    /// Return the node following the source on its shortest path to the target
+    pub fn next_hop(&self, source: usize, target: usize) -> Option<usize> {
+        self.next_hops.get(source)?.get(target).cloned().flatten()
+    }
+
+    /// Return the cost and the nodes of the shortest path from the source to the target,
+    /// or `None` if the target is unreachable or its distance overflows `W`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flex_algo::AllPairs;
+    ///
+    /// let edges = vec![(0, 1, 2.5), (1, 2, 1.0), (0, 2, 4.0)];
+    /// let all_pairs = AllPairs::floyd_warshall(4, edges).unwrap();
+    /// assert_eq!(all_pairs.path(0, 2), Some((3.5, vec![0, 1, 2])));
+    /// assert_eq!(all_pairs.path(2, 0), None);
+    /// assert_eq!(all_pairs.path(3, 3), Some((0.0, vec![3])));
+    ///
+    /// ```
+    pub fn path(&self, source: usize, target: usize) -> Option<(W, Vec<usize>)> {
+        let distance = self.distance(source, target)?;
+        let mut path = vec![source];
+        let mut vertex = source;
+        while vertex != target {
+            vertex = self.next_hops[vertex][target]?;
+            path.push(vertex);
+        }
+        Some((distance, path))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn edges() -> Vec<(usize, usize, i32)> {
+        vec![
+            (0, 1, 3), (0, 2, 8), (0, 4, -4), (1, 3, 1), (1, 4, 7),
+            (2, 1, 4), (3, 0, 2), (3, 2, -5), (4, 3, 6)
+        ]
+    }
+
+    #[test]
+    fn test_floyd_warshall() {
+        let all_pairs = AllPairs::floyd_warshall(5, edges()).unwrap();
+        println!("floyd warshall: {:?}", all_pairs);
+        let expected = [
+            vec![0, 1, -3, 2, -4],
+            vec![3, 0, -4, 1, -1],
+            vec![7, 4, 0, 5, 3],
+            vec![2, -1, -5, 0, -2],
+            vec![8, 5, 1, 6, 0],
+        ];
+        for (source, row) in expected.iter().enumerate() {
+            for (target, &distance) in row.iter().enumerate() {
+                assert_eq!(all_pairs.distance(source, target), Some(distance));
+            }
+        }
+        assert_eq!(all_pairs.path(0, 1), Some((1, vec![0, 4, 3, 2, 1])));
+        assert_eq!(all_pairs.next_hop(0, 1), Some(4));
+    }
+
+    #[test]
+    fn test_johnson() {
+        let floyd_warshall = AllPairs::floyd_warshall(6, edges()).unwrap();
+        let johnson = AllPairs::johnson(6, edges()).unwrap();
+        println!("johnson: {:?}", johnson);
+        assert_eq!(johnson.distances(), floyd_warshall.distances());
+        for source in 0..6 {
+            for target in 0..6 {
+                assert_eq!(johnson.path(source, target), floyd_warshall.path(source, target));
+            }
+        }
+        assert_eq!(johnson.path(0, 5), None);
+        assert_eq!(johnson.path(5, 5), Some((0, vec![5])));
+    }
+
+    #[test]
+    fn test_johnson_narrow_weights() {
+        // the potentials are -50 for 1, -30 for 2 and -128 for 3,
+        // 100 - (-50) overflows an i8 while 2 -> 1 reweighted is 100 - 30 + 50 = 120
+        let edges: Vec<(usize, usize, i8)> = vec![(0, 1, -50), (0, 2, -30), (2, 1, 100), (1, 3, -78)];
+        let johnson = AllPairs::johnson(4, edges.clone()).unwrap();
+        assert_eq!(johnson.distances(), AllPairs::floyd_warshall(4, edges).unwrap().distances());
+        assert_eq!(johnson.path(2, 3), Some((22, vec![2, 1, 3])));
+        assert_eq!(johnson.distance(0, 3), Some(-128));
+    }
+
+    #[test]
+    fn test_all_pairs_narrow_cross_check() {
+        // 4 -> 0 -> 1 is 27, its reweighted distance 21 + 103 + 6 doesn't fit an i8
+        let edges: Vec<(usize, usize, i8)> = vec![(3, 1, -103), (0, 1, 6), (4, 0, 21), (3, 2, -41)];
+        let johnson = AllPairs::johnson(5, edges.clone()).unwrap();
+        assert_eq!(johnson.distance(4, 1), Some(27));
+        assert_eq!(johnson, AllPairs::floyd_warshall(5, edges).unwrap());
+
+        // the potential of 1 is -100, 2 -> 1 reweighted is 127 + 0 + 100
+        let edges: Vec<(usize, usize, i8)> = vec![(0, 1, -100), (2, 1, 127)];
+        let johnson = AllPairs::johnson(3, edges.clone()).unwrap();
+        assert_eq!(johnson.distance(2, 1), Some(127));
+        assert_eq!(johnson, AllPairs::floyd_warshall(3, edges).unwrap());
+
+        // 0 -> 2 is -200 while 0 -> 3 through it is -100
+        let edges: Vec<(usize, usize, i8)> = vec![(0, 1, -100), (1, 2, -100), (2, 3, 100)];
+        let johnson = AllPairs::johnson(4, edges.clone()).unwrap();
+        assert_eq!(johnson.distance(0, 2), None);
+        assert_eq!(johnson.path(0, 3), Some((-100, vec![0, 1, 2, 3])));
+        assert_eq!(johnson, AllPairs::floyd_warshall(4, edges).unwrap());
+
+        // the whole range of i8 on random graphs, with or without a negative cycle
+        let mut seed: i64 = 5;
+        let mut random = |bound: i64| {
+            seed = (seed * 1_103_515_245 + 12_345) % 2_147_483_648;
+            seed % bound
+        };
+        for _ in 0..200 {
+            let num_nodes = 1 + random(6) as usize;
+            let edges: Vec<(usize, usize, i8)> = (0..random(10))
+                .map(|_| (random(num_nodes as i64) as usize, random(num_nodes as i64) as usize, (random(256) - 128) as i8))
+                .collect();
+            let floyd_warshall = AllPairs::floyd_warshall(num_nodes, edges.clone());
+            let johnson = AllPairs::johnson(num_nodes, edges.clone());
+            match floyd_warshall {
+                Ok(floyd_warshall) => assert_eq!(johnson.unwrap().distances(), floyd_warshall.distances()),
+                Err(_) => assert!(johnson.is_err() && BellmanFord::new(num_nodes, edges).negative_cycle().is_some()),
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "reweighted edge weight overflow")]
+    fn test_johnson_reweight_overflow() {
+        // i128 is its own wide type, 2 -> 1 reweighted is the maximum minus the minimum
+        let edges: Vec<(usize, usize, i128)> = vec![(0, 1, i128::MIN), (2, 1, i128::MAX)];
+        AllPairs::johnson(3, edges).unwrap();
+    }
+
+    #[test]
+    fn test_all_pairs_negative_cycle() {
+        let edges = vec![(0, 1, 1), (1, 2, -1), (2, 1, -1), (2, 3, 1)];
+        let cycle = AllPairs::floyd_warshall(4, edges.clone()).unwrap_err();
+        assert_eq!(cycle.nodes(), &[1, 2]);
+        assert_eq!(AllPairs::johnson(4, edges).unwrap_err(), cycle);
+    }
+
+    #[test]
+    fn test_all_pairs_narrow_negative_cycle() {
+        // -60 + -60 fits an i8 while -120 + -60 doesn't
+        let edges: Vec<(usize, usize, i8)> = vec![(0, 1, -60), (1, 0, -60), (1, 2, 3)];
+        let cycle = AllPairs::floyd_warshall(3, edges.clone()).unwrap_err();
+        assert_eq!(cycle.nodes(), &[0, 1]);
+        assert_eq!(AllPairs::johnson(3, edges).unwrap_err(), cycle);
+
+        // -100 + -100 already underflows an i8
+        let edges: Vec<(usize, usize, i8)> = vec![(0, 1, -100), (1, 0, -100)];
+        let cycle = AllPairs::floyd_warshall(2, edges.clone()).unwrap_err();
+        assert_eq!(cycle.nodes(), &[0, 1]);
+        assert_eq!(AllPairs::johnson(2, edges).unwrap_err(), cycle);
+
+        // -122 - 65 underflows before the cycle 0 -> 1 -> 0 of -124 is closed
+        let edges: Vec<(usize, usize, i8)> = vec![(2, 1, -122), (1, 0, -65), (0, 1, -59)];
+        let cycle = AllPairs::floyd_warshall(3, edges.clone()).unwrap_err();
+        assert_eq!(cycle.nodes(), &[0, 1]);
+        assert_eq!(AllPairs::johnson(3, edges).unwrap_err(), cycle);
+    }
+
+    #[test]
+    #[should_panic(expected = "distance overflow")]
+    fn test_floyd_warshall_distance_overflow() {
+        // i128 is its own wide type
+        let edges: Vec<(usize, usize, i128)> = vec![(0, 1, i128::MIN), (1, 2, -1)];
+        let _ = AllPairs::floyd_warshall(3, edges);
+    }
+}
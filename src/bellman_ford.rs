@@ -0,0 +1,460 @@
+use std::collections::VecDeque;
+use std::fmt::{Debug, Display, Formatter};
+use crate::dijkstra::{self, ShortestPaths};
+#[cfg(feature = "serde")]
+use crate::error::GraphError;
+use crate::graph;
+use crate::weight::Weight;
+
+/// Bellman-Ford algorithm
+///
+/// This crate implements the Bellman-Ford and SPFA algorithms to compute the shortest paths
+/// of a graph with negative weights, and report a negative cycle when there is one.
+///
+#[derive(Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "BellmanFordData<W>", bound(deserialize = "W: Weight + serde::Deserialize<'de>"))
+)]
+pub struct BellmanFord<W> {
+    edges: Vec<(usize, usize, W)>,
+    // rebuilt from the edges when deserialized
+    #[cfg_attr(feature = "serde", serde(skip_serializing))]
+    adjacent_list: Vec<Vec<(usize, W)>>,
+    num_nodes: usize,
+}
+
+// the unchecked deserialized edges, whose nodes and weights are checked as by `new`
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+#[serde(rename = "BellmanFord")]
+struct BellmanFordData<W> {
+    edges: Vec<(usize, usize, W)>,
+    num_nodes: usize,
+}
+
+#[cfg(feature = "serde")]
+impl<W: Weight> TryFrom<BellmanFordData<W>> for BellmanFord<W> {
+    type Error = GraphError;
+
+    fn try_from(data: BellmanFordData<W>) -> Result<Self, GraphError> {
+        for &(source, target, weight) in &data.edges {
+            if let Some(node) = [source, target].into_iter().find(|&node| node >= data.num_nodes) {
+                return Err(GraphError::InvalidNode(node));
+            }
+            if !weight.is_valid() {
+                return Err(GraphError::InvalidWeight(format!("{:?}", weight)));
+            }
+        }
+        Ok(BellmanFord::new(data.num_nodes, data.edges))
+    }
+}
+
+impl<W: Weight> BellmanFord<W> {
+    /// Create a new Bellman-Ford graph with edges tuple(current, neighbor, weight) Vec
+    ///
+    /// # Panics
+    ///
+    /// Panics if an edge refers to a node out of range or if a weight is NaN.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flex_algo::BellmanFord;
+    ///
+    /// let edges = vec![(0, 1, 4), (0, 2, 5), (1, 2, -3), (2, 3, 4)];
+    /// let bellman_ford = BellmanFord::new(4, edges);
+    ///
+    /// ```
+    pub fn new(num_nodes: usize, edges: Vec<(usize, usize, W)>) -> Self {
+        let mut adjacent_list = vec![Vec::new(); num_nodes];
+        for &(source, target, weight) in &edges {
+            assert!(source < num_nodes, "invalid node: {}", source);
+            assert!(target < num_nodes, "invalid node: {}", target);
+            assert!(weight.is_valid(), "invalid edge weight: {:?}", weight);
+            adjacent_list[source].push((target, weight));
+        }
+        BellmanFord {
+            edges,
+            adjacent_list,
+            num_nodes,
+        }
+    }
+
+    // the distances are kept in the wide type, where the n - 1 rounds can't overflow unless W is its own wide type
+    fn _relax(distances: &mut [Option<W::Wide>], predecessors: &mut [Option<usize>], edge: (usize, usize, W)) -> Result<Relax, NegativeCycle> {
+        let (source, target, weight) = edge;
+        let distance = match distances[source] {
+            Some(distance) => distance,
+            None => return Ok(Relax::Unchanged),
+        };
+        let distance = match distance.checked_add(weight.widen()) {
+            Some(distance) => distance,
+            // a distance below the minimum of the wide type either closes a negative cycle or doesn't fit it
+            None if weight < W::zero() => return match Self::_closed_cycle(predecessors, source, target) {
+                Some(cycle) => Err(cycle),
+                None => Ok(Relax::Underflow),
+            },
+            // an overflowing distance is not followed, the target is overflowed if it's never reached otherwise
+            None => return Ok(Relax::Unchanged),
+        };
+        if distances[target].is_none_or(|current| distance < current) {
+            distances[target] = Some(distance);
+            predecessors[target] = Some(source);
+            return Ok(Relax::Improved);
+        }
+        Ok(Relax::Unchanged)
+    }
+
+    // the edge from the source to the target underflows, it closes a negative cycle if the target is on
+    // the predecessor path of the source, as the weight of the path is at most d(source) - d(target),
+    // or if that path already loops, a cycle of predecessors is always negative
+    fn _closed_cycle(predecessors: &mut [Option<usize>], source: usize, target: usize) -> Option<NegativeCycle> {
+        let mut vertex = source;
+        for _ in 0..predecessors.len() {
+            if vertex == target {
+                predecessors[target] = Some(source);
+                return Some(NegativeCycle::from_predecessors(predecessors, target));
+            }
+            vertex = predecessors[vertex]?;
+        }
+        Some(NegativeCycle::from_predecessors(predecessors, source))
+    }
+
+    fn _bellman_ford(&self, distances: &mut [Option<W::Wide>], predecessors: &mut [Option<usize>]) -> Result<(), NegativeCycle> {
+        let mut relaxed = None;
+        let mut underflow = false;
+        // n - 1 rounds settle every shortest path, a relaxation in the n-th round means a negative cycle
+        for _ in 0..self.num_nodes {
+            relaxed = None;
+            for &edge in &self.edges {
+                match Self::_relax(distances, predecessors, edge)? {
+                    Relax::Improved => relaxed = Some(edge.1),
+                    Relax::Underflow => underflow = true,
+                    Relax::Unchanged => {}
+                }
+            }
+            if relaxed.is_none() {
+                break;
+            }
+        }
+        match relaxed {
+            Some(node) => Err(NegativeCycle::from_predecessors(predecessors, node)),
+            None => {
+                // without a negative cycle, a path really is shorter than the minimum of the wide type
+                assert!(!underflow, "distance overflow");
+                Ok(())
+            }
+        }
+    }
+
+    /// Return the shortest paths from the source node by the Bellman-Ford algorithm,
+    /// or a negative cycle reachable from the source
+    ///
+    /// A node whose shortest distance doesn't fit `W` has no distance,
+    /// it is reported by `ShortestPaths::is_overflowed` instead of as unreachable.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a shortest path doesn't even fit the wide type of `W` without a negative cycle,
+    /// which only happens to the weights that are their own wide type.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flex_algo::BellmanFord;
+    ///
+    /// let edges = vec![(0, 1, 4), (0, 2, 5), (1, 2, -3), (2, 3, 4)];
+    /// let bellman_ford = BellmanFord::new(4, edges);
+    /// let paths = bellman_ford.shortest_paths(0).unwrap();
+    /// assert_eq!(paths.distances(), &[Some(0), Some(4), Some(1), Some(5)]);
+    /// assert_eq!(paths.path_to(3), Some((5, vec![0, 1, 2, 3])));
+    ///
+    /// let edges = vec![(0, 1, 4), (1, 2, -3), (2, 3, 4), (3, 1, -2)];
+    /// let bellman_ford = BellmanFord::new(4, edges);
+    /// let cycle = bellman_ford.shortest_paths(0).unwrap_err();
+    /// assert_eq!(cycle.nodes(), &[1, 2, 3]);
+    ///
+    /// ```
+    pub fn shortest_paths(&self, source: usize) -> Result<ShortestPaths<W>, NegativeCycle> {
+        let mut distances = vec![None; self.num_nodes];
+        let mut predecessors = vec![None; self.num_nodes];
+        distances[source] = Some(W::zero().widen());
+        self._bellman_ford(&mut distances, &mut predecessors)?;
+        Ok(self._paths(source, distances, predecessors))
+    }
+
+    /// Return the shortest paths from the source node by the SPFA (Shortest Path Faster Algorithm),
+    /// a queue based Bellman-Ford which only relaxes the edges of the nodes whose distance changed,
+    /// the overflowed nodes are reported as by `shortest_paths`
+    ///
+    /// # Panics
+    ///
+    /// Panics as `shortest_paths` does.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flex_algo::BellmanFord;
+    ///
+    /// let edges = vec![(0, 1, 4), (0, 2, 5), (1, 2, -3), (2, 3, 4)];
+    /// let bellman_ford = BellmanFord::new(4, edges);
+    /// let paths = bellman_ford.spfa(0).unwrap();
+    /// assert_eq!(paths.path_to(3), Some((5, vec![0, 1, 2, 3])));
+    ///
+    /// ```
+    pub fn spfa(&self, source: usize) -> Result<ShortestPaths<W>, NegativeCycle> {
+        let mut distances = vec![None; self.num_nodes];
+        let mut predecessors = vec![None; self.num_nodes];
+        let mut in_queue = vec![false; self.num_nodes];
+        let mut counts = vec![0; self.num_nodes];
+        let mut queue = VecDeque::new();
+        distances[source] = Some(W::zero().widen());
+        queue.push_back(source);
+        in_queue[source] = true;
+
+        let mut underflow = false;
+        while let Some(vertex) = queue.pop_front() {
+            in_queue[vertex] = false;
+            for &(neighbor, weight) in &self.adjacent_list[vertex] {
+                let relax = Self::_relax(&mut distances, &mut predecessors, (vertex, neighbor, weight))?;
+                underflow |= relax == Relax::Underflow;
+                if relax == Relax::Improved && !in_queue[neighbor] {
+                    counts[neighbor] += 1;
+                    // a node queued n times is improved by a negative cycle,
+                    // Bellman-Ford gives the cycle back
+                    if counts[neighbor] >= self.num_nodes {
+                        return self.shortest_paths(source);
+                    }
+                    queue.push_back(neighbor);
+                    in_queue[neighbor] = true;
+                }
+            }
+        }
+        // the skipped underflow may hide a negative cycle, Bellman-Ford tells them apart
+        if underflow {
+            return self.shortest_paths(source);
+        }
+        Ok(self._paths(source, distances, predecessors))
+    }
+
+    // a distance that doesn't fit W overflowed, as did an edge from a node with a distance to a node without one
+    fn _paths(&self, source: usize, wide: Vec<Option<W::Wide>>, predecessors: Vec<Option<usize>>) -> ShortestPaths<W> {
+        let mut overflow = vec![false; self.num_nodes];
+        for &(source, target, _) in &self.edges {
+            if wide[source].is_some() && wide[target].is_none() {
+                overflow[target] = true;
+            }
+        }
+        let distances: Vec<Option<W>> = wide
+            .into_iter()
+            .enumerate()
+            .map(|(node, distance)| {
+                let distance = W::narrow(distance?);
+                overflow[node] |= distance.is_none();
+                distance
+            })
+            .collect();
+        let neighbors = |node: usize| self.adjacent_list[node].iter().map(|&(neighbor, _)| neighbor);
+        let overflowed = dijkstra::_overflowed(&distances, overflow, neighbors);
+        ShortestPaths::new(source, distances, predecessors, overflowed)
+    }
+
+    /// Return a negative cycle anywhere in the graph, or `None` if there is none
+    ///
+    /// # Panics
+    ///
+    /// Panics as `shortest_paths` does.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flex_algo::BellmanFord;
+    ///
+    /// let edges = vec![(0, 1, 4), (2, 3, 4), (3, 4, -2), (4, 2, -3)];
+    /// let bellman_ford = BellmanFord::new(5, edges);
+    /// // the cycle is not reachable from 0
+    /// assert!(bellman_ford.shortest_paths(0).is_ok());
+    /// assert_eq!(bellman_ford.negative_cycle().unwrap().nodes(), &[2, 3, 4]);
+    ///
+    /// ```
+    pub fn negative_cycle(&self) -> Option<NegativeCycle> {
+        self.potentials().err()
+    }
+
+    // the distances from a virtual source connected to every node by a zero weight edge, in the wide type
+    pub(crate) fn potentials(&self) -> Result<Vec<W::Wide>, NegativeCycle> {
+        let mut distances = vec![Some(W::zero().widen()); self.num_nodes];
+        let mut predecessors = vec![None; self.num_nodes];
+        self._bellman_ford(&mut distances, &mut predecessors)?;
+        Ok(distances.into_iter().map(|distance| distance.unwrap()).collect())
+    }
+}
+
+// the outcome of relaxing an edge
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Relax {
+    Improved,
+    Unchanged,
+    Underflow,
+}
+
+/// Negative cycle
+///
+/// The nodes of a cycle whose total weight is negative, in the order of its edges
+/// and starting from its smallest node.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NegativeCycle {
+    nodes: Vec<usize>,
+}
+
+impl NegativeCycle {
+    // the node relaxed in the n-th round is reached from the cycle
+    fn from_predecessors(predecessors: &[Option<usize>], node: usize) -> Self {
+        NegativeCycle {
+            nodes: graph::_cycle_nodes(predecessors, node),
+        }
+    }
+
+    /// Return the nodes of the cycle
+    pub fn nodes(&self) -> &[usize] {
+        &self.nodes
+    }
+}
+
+impl Display for NegativeCycle {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "negative cycle: ")?;
+        for node in &self.nodes {
+            write!(f, "{} -> ", node)?;
+        }
+        write!(f, "{}", self.nodes[0])
+    }
+}
+
+impl std::error::Error for NegativeCycle {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bellman_ford() {
+        let edges = vec![
+            (0, 1, 6), (0, 2, 7), (1, 2, 8), (1, 3, 5), (1, 4, -4),
+            (2, 3, -3), (2, 4, 9), (3, 1, -2), (4, 0, 2), (4, 3, 7)
+        ];
+        let bellman_ford = BellmanFord::new(6, edges);
+        println!("bellman ford: {:?}", bellman_ford);
+        let paths = bellman_ford.shortest_paths(0).unwrap();
+        assert_eq!(paths.distances(), &[Some(0), Some(2), Some(7), Some(4), Some(-2), None]);
+        assert_eq!(paths.path_to(4), Some((-2, vec![0, 2, 3, 1, 4])));
+        assert_eq!(paths.path_to(5), None);
+        assert_eq!(bellman_ford.spfa(0).unwrap(), paths);
+        assert_eq!(bellman_ford.negative_cycle(), None);
+    }
+
+    #[test]
+    fn test_bellman_ford_negative_cycle() {
+        let edges = vec![(0, 1, 1), (1, 2, 2), (2, 3, -1), (3, 4, -1), (4, 2, -1), (4, 5, 1)];
+        let bellman_ford = BellmanFord::new(6, edges);
+        let cycle = bellman_ford.shortest_paths(0).unwrap_err();
+        println!("{}", cycle);
+        assert_eq!(cycle.nodes(), &[2, 3, 4]);
+        assert_eq!(cycle.to_string(), "negative cycle: 2 -> 3 -> 4 -> 2");
+        assert_eq!(bellman_ford.spfa(0).unwrap_err(), cycle);
+        assert_eq!(bellman_ford.negative_cycle(), Some(cycle));
+        // the cycle is not reachable from 5
+        assert!(bellman_ford.shortest_paths(5).is_ok());
+    }
+
+    #[test]
+    fn test_bellman_ford_self_loop() {
+        let bellman_ford = BellmanFord::new(2, vec![(0, 1, 3.5), (1, 1, -0.5)]);
+        assert_eq!(bellman_ford.spfa(0).unwrap_err().nodes(), &[1]);
+    }
+
+    #[test]
+    fn test_bellman_ford_narrow_negative_cycle() {
+        // -100 + -100 underflows an i8
+        let bellman_ford = BellmanFord::new(3, vec![(0, 1, -100i8), (1, 0, -100), (1, 2, 5)]);
+        let cycle = bellman_ford.shortest_paths(0).unwrap_err();
+        assert_eq!(cycle.nodes(), &[0, 1]);
+        assert_eq!(bellman_ford.spfa(0).unwrap_err(), cycle);
+        assert_eq!(bellman_ford.spfa(2).unwrap(), bellman_ford.shortest_paths(2).unwrap());
+        assert_eq!(bellman_ford.negative_cycle(), Some(cycle));
+
+        // the cycle 2 -> 3 -> 2 is reached through distances below the minimum of i8
+        let edges = vec![(0, 1, -120i8), (1, 2, -5), (2, 3, -1), (3, 2, -1), (3, 4, -100)];
+        let bellman_ford = BellmanFord::new(5, edges);
+        assert_eq!(bellman_ford.shortest_paths(0).unwrap_err().nodes(), &[2, 3]);
+        assert_eq!(bellman_ford.spfa(0).unwrap_err().nodes(), &[2, 3]);
+
+        // -122 - 65 underflows before the cycle 0 -> 1 -> 0 of -124 is closed
+        let bellman_ford = BellmanFord::new(3, vec![(2, 1, -122i8), (1, 0, -65), (0, 1, -59)]);
+        let cycle = bellman_ford.shortest_paths(2).unwrap_err();
+        assert_eq!(cycle.nodes(), &[0, 1]);
+        assert_eq!(bellman_ford.spfa(2).unwrap_err(), cycle);
+        assert_eq!(bellman_ford.negative_cycle(), Some(cycle));
+    }
+
+    #[test]
+    fn test_bellman_ford_wide_negative_cycle() {
+        // i128 is its own wide type, the underflow closes the cycle
+        let bellman_ford = BellmanFord::new(3, vec![(0, 1, i128::MIN / 2 - 1), (1, 0, i128::MIN / 2), (1, 2, 5)]);
+        assert_eq!(bellman_ford.shortest_paths(0).unwrap_err().nodes(), &[0, 1]);
+        assert_eq!(bellman_ford.spfa(0).unwrap_err().nodes(), &[0, 1]);
+    }
+
+    #[test]
+    fn test_bellman_ford_overflowed() {
+        // the distance 100 + 100 of 2 doesn't fit an i8, the distance 100 of 3 through 2 does
+        let bellman_ford = BellmanFord::new(5, vec![(0, 1, 100i8), (1, 2, 100), (2, 3, -100), (4, 3, 1)]);
+        let paths = bellman_ford.shortest_paths(0).unwrap();
+        assert_eq!(paths.distances(), &[Some(0), Some(100), None, Some(100), None]);
+        assert_eq!(paths.overflowed(), &[2]);
+        assert_eq!(paths.unreachable(), vec![4]);
+        assert_eq!(paths.path_to(3), Some((100, vec![0, 1, 2, 3])));
+        assert_eq!(bellman_ford.spfa(0).unwrap(), paths);
+
+        // -100 - 100 doesn't fit either
+        let bellman_ford = BellmanFord::new(3, vec![(0, 1, -100i8), (1, 2, -100)]);
+        let paths = bellman_ford.shortest_paths(0).unwrap();
+        assert_eq!(paths.distances(), &[Some(0), Some(-100), None]);
+        assert_eq!(paths.overflowed(), &[2]);
+    }
+
+    #[test]
+    #[should_panic(expected = "distance overflow")]
+    fn test_bellman_ford_distance_overflow() {
+        let bellman_ford = BellmanFord::new(3, vec![(0, 1, i128::MIN), (1, 2, -1)]);
+        let _ = bellman_ford.shortest_paths(0);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_bellman_ford_serde() {
+        let bellman_ford = BellmanFord::new(4, vec![(0, 1, 4), (1, 2, -3), (2, 3, 4), (3, 1, -2)]);
+        let json = serde_json::to_string(&bellman_ford).unwrap();
+        assert_eq!(json, r#"{"edges":[[0,1,4],[1,2,-3],[2,3,4],[3,1,-2]],"num_nodes":4}"#);
+        let bellman_ford: BellmanFord<i32> = serde_json::from_str(&json).unwrap();
+        assert_eq!(bellman_ford.spfa(0).unwrap_err().nodes(), &[1, 2, 3]);
+
+        // the nodes and the weights are checked as by new
+        let json = r#"{"edges":[[0,1,4],[1,3,2]],"num_nodes":2}"#;
+        let error = serde_json::from_str::<BellmanFord<i32>>(json).unwrap_err();
+        assert!(error.to_string().starts_with("invalid node: 3"));
+        let json = r#"{"edges":[[0,1,4]],"adjacent_list":[[],[[5,1]]],"num_nodes":2}"#;
+        let bellman_ford: BellmanFord<i32> = serde_json::from_str(json).unwrap();
+        assert_eq!(bellman_ford.spfa(1).unwrap().reachable(), vec![1]);
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid node: 2")]
+    fn test_bellman_ford_invalid_node() {
+        BellmanFord::new(2, vec![(0, 2, 1)]);
+    }
+}
@@ -1,3 +1,5 @@
+#![allow(clippy::new_without_default)]
+
 use std::fmt::Debug;
 
 /// BinarySearchTree
@@ -6,9 +8,11 @@ use std::fmt::Debug;
 /// preorder traversal, search and validate algorithms.
 /// 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BST<T>(Option<Box<BinaryNode<T>>>);
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BinaryNode<T> {
     data: T,
     left: BST<T>,
@@ -179,6 +183,7 @@ impl<T: Debug> BST<T> {
 }
 
 #[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
 mod tests {
     use super::*;
 
@@ -214,4 +219,17 @@ mod tests {
         let found = bst.search(2);
         assert_eq!(found, Some(2));
     }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_bst_serde() {
+        let mut bst = BST::new();
+        bst.insert(3);
+        bst.insert(2);
+        bst.insert(1);
+        let json = serde_json::to_string(&bst).unwrap();
+        let bst: BST<i32> = serde_json::from_str(&json).unwrap();
+        assert!(bst.is_valid(i32::MIN, i32::MAX));
+        assert_eq!(bst.search(2), Some(2));
+    }
 }
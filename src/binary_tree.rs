@@ -1,3 +1,5 @@
+#![allow(clippy::new_without_default, clippy::ptr_arg, clippy::len_zero, clippy::get_first, clippy::needless_return, clippy::unnecessary_cast, clippy::useless_vec)]
+
 use std::fmt::{Debug, Display, Formatter, Result};
 
 /// BinaryTree
@@ -6,9 +8,11 @@ use std::fmt::{Debug, Display, Formatter, Result};
 /// level order, left/right side view, build a complete tree with count nodes algorithms.
 /// 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BinaryTree<T>(Option<Box<BinaryNode<T>>>);
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BinaryNode<T> {
     data: T,
     left: BinaryTree<T>,
@@ -542,4 +546,15 @@ mod tests {
         assert_eq!(count, 7);
         // panic!();
     }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_binary_tree_serde() {
+        let mut tree = BinaryTree::new();
+        let v = vec![Some(1), Some(2), Some(3), None, None, Some(4), Some(5), Some(6)];
+        tree.insert(&v);
+        let json = serde_json::to_string(&tree).unwrap();
+        let tree: BinaryTree<i32> = serde_json::from_str(&json).unwrap();
+        assert_eq!(tree.level_order(), vec![vec![1], vec![2, 3], vec![4, 5], vec![6]]);
+    }
 }
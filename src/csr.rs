@@ -0,0 +1,476 @@
+use std::fmt::Debug;
+use crate::dijkstra::{Dijkstra, ShortestPaths};
+use crate::graph::{BreadthFirstSearch, Cycle, DepthFirstSearch, Graph};
+use crate::visit::{self, GraphBase, IntoEdges, IntoNeighbors, NodeCount};
+use crate::weight::Weight;
+
+/// Compressed sparse row graph
+///
+/// This crate implements a frozen directed graph in the compressed sparse row layout: the targets and the weights
+/// of all the edges in two flat vectors sorted by source, with the offset of the first edge of every node.
+/// The weights are the edge payloads, an unweighted `CsrGraph<()>` stores none of them.
+///
+/// The node ids are the ones of the graph it is built from, a removed node of a `Graph` stays removed:
+/// it has no edges and it is skipped by `node_count`, `dfs_all` and `topological_sort`.
+///
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "CsrData<W>", bound(deserialize = "W: serde::Deserialize<'de>"))
+)]
+pub struct CsrGraph<W = ()> {
+    // the edges of the node i are offsets[i]..offsets[i + 1]
+    offsets: Vec<usize>,
+    targets: Vec<usize>,
+    weights: Vec<W>,
+    // the liveness of every node, false for the removed nodes of a `Graph`
+    live: Vec<bool>,
+    node_count: usize,
+}
+
+// the unchecked deserialized layout, whose invariants are checked before it is used
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+#[serde(rename = "CsrGraph")]
+struct CsrData<W> {
+    offsets: Vec<usize>,
+    targets: Vec<usize>,
+    weights: Vec<W>,
+    live: Vec<bool>,
+    node_count: usize,
+}
+
+#[cfg(feature = "serde")]
+impl<W> TryFrom<CsrData<W>> for CsrGraph<W> {
+    type Error = String;
+
+    fn try_from(data: CsrData<W>) -> Result<Self, String> {
+        let num_nodes = data.live.len();
+        if data.offsets.len() != num_nodes + 1 || data.offsets[0] != 0 || data.offsets[num_nodes] != data.targets.len() {
+            return Err("invalid offsets".to_string());
+        }
+        if data.weights.len() != data.targets.len() {
+            return Err(format!("invalid number of weights: {}", data.weights.len()));
+        }
+        if data.node_count != data.live.iter().filter(|&&live| live).count() {
+            return Err(format!("invalid node count: {}", data.node_count));
+        }
+        for node in 0..num_nodes {
+            let (start, end) = (data.offsets[node], data.offsets[node + 1]);
+            if start > end || (!data.live[node] && start < end) {
+                return Err(format!("invalid offsets of the node: {}", node));
+            }
+        }
+        // a removed node has no incoming edges either
+        if let Some(&target) = data.targets.iter().find(|&&target| !data.live.get(target).copied().unwrap_or(false)) {
+            return Err(format!("invalid node: {}", target));
+        }
+        Ok(CsrGraph {
+            offsets: data.offsets,
+            targets: data.targets,
+            weights: data.weights,
+            live: data.live,
+            node_count: data.node_count,
+        })
+    }
+}
+
+impl CsrGraph {
+    /// Create a new graph with the given routes tuple(current, neighbor) current <--- neighbor as `Graph::new`
+    ///
+    /// # Panics
+    ///
+    /// Panics if a route refers to a node out of range.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flex_algo::CsrGraph;
+    ///
+    /// let graph = CsrGraph::new(6, vec![(1, 0), (2, 1), (2, 5), (0, 3), (4, 3), (3, 5), (4, 5)]);
+    /// assert_eq!(graph.topological_sort(), Ok(vec![5, 3, 0, 4, 1, 2]));
+    /// ```
+    pub fn new(num_nodes: usize, routes: Vec<(usize, usize)>) -> Self {
+        let edges = routes.into_iter().map(|(target, source)| (source, target, ())).collect();
+        CsrGraph::from_edges(num_nodes, edges)
+    }
+}
+
+impl<W> CsrGraph<W> {
+    /// Create a new graph with edges tuple(current, neighbor, weight) Vec,
+    /// the edges of every node keep their order
+    ///
+    /// # Panics
+    ///
+    /// Panics if an edge refers to a node out of range.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flex_algo::CsrGraph;
+    ///
+    /// let graph = CsrGraph::from_edges(3, vec![(1, 2, 0.5), (0, 1, 1.5), (0, 2, 4.0)]);
+    /// assert_eq!(graph.neighbors(0), &[1, 2]);
+    /// assert_eq!(graph.weights(0), &[1.5, 4.0]);
+    /// assert_eq!(graph.shortest_paths(0).path_to(2), Some((2.0, vec![0, 1, 2])));
+    /// ```
+    pub fn from_edges(num_nodes: usize, edges: Vec<(usize, usize, W)>) -> Self {
+        let mut offsets = vec![0; num_nodes + 1];
+        for &(source, target, _) in &edges {
+            assert!(source < num_nodes, "invalid node: {}", source);
+            assert!(target < num_nodes, "invalid node: {}", target);
+            offsets[source + 1] += 1;
+        }
+        for node in 0..num_nodes {
+            offsets[node + 1] += offsets[node];
+        }
+
+        // counting sort, every edge goes to the next free slot of its source
+        let mut next = offsets.clone();
+        let mut targets = vec![0; edges.len()];
+        let mut slots: Vec<Option<W>> = edges.iter().map(|_| None).collect();
+        for (source, target, weight) in edges {
+            targets[next[source]] = target;
+            slots[next[source]] = Some(weight);
+            next[source] += 1;
+        }
+        CsrGraph {
+            offsets,
+            targets,
+            weights: slots.into_iter().map(|weight| weight.unwrap()).collect(),
+            live: vec![true; num_nodes],
+            node_count: num_nodes,
+        }
+    }
+
+    /// Return the number of nodes, without the removed ones
+    pub fn node_count(&self) -> usize {
+        self.node_count
+    }
+
+    /// Return the upper bound of the node ids, the removed nodes included
+    pub fn node_bound(&self) -> usize {
+        self.offsets.len() - 1
+    }
+
+    /// Return true if the node exists
+    pub fn contains_node(&self, node: usize) -> bool {
+        self.live.get(node).copied().unwrap_or(false)
+    }
+
+    /// Return the number of edges
+    pub fn edge_count(&self) -> usize {
+        self.targets.len()
+    }
+
+    /// Return the targets of the outgoing edges of the node
+    ///
+    /// # Panics
+    ///
+    /// Panics if the node is out of range.
+    ///
+    pub fn neighbors(&self, node: usize) -> &[usize] {
+        &self.targets[self.offsets[node]..self.offsets[node + 1]]
+    }
+
+    /// Return the weights of the outgoing edges of the node, in the order of `neighbors`
+    ///
+    /// # Panics
+    ///
+    /// Panics if the node is out of range.
+    ///
+    pub fn weights(&self, node: usize) -> &[W] {
+        &self.weights[self.offsets[node]..self.offsets[node + 1]]
+    }
+
+    /// Return the number of outgoing edges of the node
+    ///
+    /// # Panics
+    ///
+    /// Panics if the node is out of range.
+    ///
+    pub fn outdegree(&self, node: usize) -> usize {
+        self.offsets[node + 1] - self.offsets[node]
+    }
+
+    /// Return the visit order of the breadth first search from the start node
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start node is out of range.
+    ///
+    pub fn breadth_first_search(&self, start: usize) -> Vec<usize> {
+        self.bfs(start).order
+    }
+
+    /// Breadth first search from the start node as `Graph::bfs`
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start node is out of range.
+    ///
+    pub fn bfs(&self, start: usize) -> BreadthFirstSearch {
+        visit::bfs(self, start)
+    }
+
+    /// Return the postorder of the depth first search from the node
+    ///
+    /// # Panics
+    ///
+    /// Panics if the node is out of range.
+    ///
+    pub fn depth_first_search(&self, vertex: usize) -> Vec<usize> {
+        self.dfs(vertex).postorder
+    }
+
+    /// Depth first search from the start node as `Graph::dfs`
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start node is out of range.
+    ///
+    pub fn dfs(&self, start: usize) -> DepthFirstSearch {
+        visit::dfs(self, start)
+    }
+
+    /// Depth first search of the whole graph as `Graph::dfs_all`
+    pub fn dfs_all(&self) -> DepthFirstSearch {
+        visit::dfs_all(self)
+    }
+
+    /// Return the topological order of the nodes by the Kahn algorithm as `Graph::topological_sort`,
+    /// or a cycle when the graph is not acyclic
+    pub fn topological_sort(&self) -> Result<Vec<usize>, Cycle> {
+        visit::topological_sort(self)
+    }
+}
+
+impl<W: Weight> CsrGraph<W> {
+    /// Return the distances and the predecessor tree of the shortest paths from the source node
+    /// by the Dijkstra algorithm as `Dijkstra::shortest_paths`
+    ///
+    /// # Panics
+    ///
+    /// Panics if the source node is out of range, or if the weight of an edge reached from the source
+    /// is negative or NaN.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flex_algo::{CsrGraph, Dijkstra};
+    ///
+    /// let times = vec![(0, 1, 9), (0, 3, 2), (1, 4, 1), (3, 1, 4), (3, 4, 6), (2, 1, 3), (4, 2, 7), (2, 0, 5)];
+    /// let dijkstra = Dijkstra::new(5, times);
+    /// let graph = CsrGraph::from(&dijkstra);
+    /// assert_eq!(graph.shortest_paths(0), dijkstra.shortest_paths(0));
+    /// ```
+    pub fn shortest_paths(&self, source: usize) -> ShortestPaths<W> {
+        // the weights are checked as the edges are relaxed, not once more per query
+        visit::dijkstra(self, source)
+    }
+}
+
+impl<W> GraphBase for CsrGraph<W> {
+    fn node_bound(&self) -> usize {
+        CsrGraph::node_bound(self)
+    }
+
+    fn contains_node(&self, node: usize) -> bool {
+        CsrGraph::contains_node(self, node)
+    }
+}
+
+impl<W> NodeCount for CsrGraph<W> {
+    fn node_count(&self) -> usize {
+        CsrGraph::node_count(self)
+    }
+}
+
+impl<W> IntoNeighbors for &CsrGraph<W> {
+    fn neighbors(self, node: usize) -> impl Iterator<Item = usize> {
+        CsrGraph::neighbors(self, node).iter().copied()
+    }
+}
+
+impl<W: Copy> IntoEdges for &CsrGraph<W> {
+    type EdgeWeight = W;
+
+    fn edges(self, node: usize) -> impl Iterator<Item = (usize, W)> {
+        CsrGraph::neighbors(self, node).iter().copied().zip(self.weights(node).iter().copied())
+    }
+}
+
+impl<N, E: Clone> From<&Graph<N, E>> for CsrGraph<E> {
+    /// Create a frozen copy of the graph, the edges of every node keep their order
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flex_algo::{CsrGraph, Graph};
+    ///
+    /// let graph = Graph::new(4, vec![(1, 0), (2, 1), (3, 1), (0, 3)]);
+    /// let csr = CsrGraph::from(&graph);
+    /// assert_eq!(csr.dfs_all().edges(), graph.dfs_all().edges());
+    /// assert_eq!(csr.topological_sort(), graph.topological_sort());
+    /// ```
+    fn from(graph: &Graph<N, E>) -> Self {
+        let mut offsets = Vec::with_capacity(graph.node_bound() + 1);
+        let mut targets = Vec::with_capacity(graph.edge_count());
+        let mut weights = Vec::with_capacity(graph.edge_count());
+        offsets.push(0);
+        for node in 0..graph.node_bound() {
+            if graph.contains_node(node) {
+                for edge in graph.out_edges(node) {
+                    targets.push(edge.target());
+                    weights.push(edge.weight().clone());
+                }
+            }
+            offsets.push(targets.len());
+        }
+        CsrGraph {
+            offsets,
+            targets,
+            weights,
+            live: (0..graph.node_bound()).map(|node| graph.contains_node(node)).collect(),
+            node_count: graph.node_count(),
+        }
+    }
+}
+
+impl<W: Weight> From<&Dijkstra<W>> for CsrGraph<W> {
+    /// Create a frozen copy of the weighted graph of the Dijkstra graph
+    fn from(dijkstra: &Dijkstra<W>) -> Self {
+        CsrGraph::from(dijkstra.graph())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // a pseudo random graph with the given number of nodes and edges
+    fn edges(num_nodes: usize, num_edges: usize) -> Vec<(usize, usize, u32)> {
+        let mut seed: u64 = 17;
+        let mut next = || {
+            seed = seed.wrapping_mul(6_364_136_223_846_793_005).wrapping_add(1_442_695_040_888_963_407);
+            (seed >> 33) as usize
+        };
+        (0..num_edges).map(|_| (next() % num_nodes, next() % num_nodes, (next() % 100) as u32)).collect()
+    }
+
+    #[test]
+    fn test_csr_graph() {
+        let graph = CsrGraph::new(6, vec![(1, 0), (2, 1), (2, 5), (0, 3), (4, 3), (3, 5), (4, 5)]);
+        println!("csr graph: {:?}", graph);
+        assert_eq!(graph.node_count(), 6);
+        assert_eq!(graph.edge_count(), 7);
+        assert_eq!(graph.neighbors(5), &[2, 3, 4]);
+        assert_eq!(graph.outdegree(2), 0);
+        assert_eq!(graph.breadth_first_search(5), vec![5, 2, 3, 4, 0, 1]);
+        assert_eq!(graph.depth_first_search(5), vec![2, 1, 0, 4, 3, 5]);
+
+        let graph = CsrGraph::new(4, vec![(1, 0), (2, 1), (3, 2), (1, 3)]);
+        assert_eq!(graph.topological_sort().unwrap_err().nodes(), &[1, 2, 3]);
+        assert_eq!(CsrGraph::new(0, vec![]).topological_sort(), Ok(vec![]));
+    }
+
+    #[test]
+    fn test_csr_graph_same_as_graph() {
+        // the traversals are the same on both layouts
+        let edges = edges(500, 3000);
+        let graph = Graph::from_edges(vec![(); 500], edges.clone());
+        let dijkstra = Dijkstra::new(500, edges.clone());
+        let csr = CsrGraph::from_edges(500, edges.clone());
+        assert_eq!(CsrGraph::from(&graph), csr);
+        assert_eq!(CsrGraph::from(&dijkstra), csr);
+        for start in [0, 7, 499] {
+            assert_eq!(csr.bfs(start), graph.bfs(start));
+            assert_eq!(csr.dfs(start), graph.dfs(start));
+            assert_eq!(csr.shortest_paths(start), dijkstra.shortest_paths(start));
+        }
+        assert_eq!(csr.dfs_all(), graph.dfs_all());
+
+        // a dag from the smaller to the larger node
+        let dag: Vec<(usize, usize, ())> = edges.iter().filter(|&&(source, target, _)| source < target).map(|&(source, target, _)| (source, target, ())).collect();
+        let graph = Graph::from_edges(vec![(); 500], dag.clone());
+        let csr = CsrGraph::from_edges(500, dag);
+        assert_eq!(csr.topological_sort(), graph.topological_sort());
+        assert!(csr.topological_sort().is_ok());
+    }
+
+    #[test]
+    fn test_csr_graph_removed_node() {
+        let mut graph = Graph::new(3, vec![(1, 0), (2, 1)]);
+        graph.remove_node(1);
+        let csr = CsrGraph::from(&graph);
+        assert_eq!(csr.node_count(), 2);
+        assert_eq!(csr.node_bound(), 3);
+        assert!(!csr.contains_node(1));
+        assert_eq!(csr.edge_count(), 0);
+        assert_eq!(csr.topological_sort(), Ok(vec![0, 2]));
+        assert_eq!(csr.topological_sort(), graph.topological_sort());
+        assert_eq!(csr.dfs_all(), graph.dfs_all());
+    }
+
+    #[test]
+    fn test_csr_graph_deep() {
+        let n = 200_000;
+        let graph = CsrGraph::new(n, (1..n).map(|node| (node, node - 1)).collect());
+        assert_eq!(graph.depth_first_search(0).len(), n);
+        assert_eq!(graph.topological_sort().unwrap().len(), n);
+    }
+
+    #[test]
+    fn test_csr_graph_edge_order() {
+        // the edges of every node keep their input order
+        let graph = CsrGraph::from_edges(3, vec![(2, 0, 'a'), (0, 2, 'b'), (2, 1, 'c'), (0, 1, 'd'), (2, 2, 'e')]);
+        assert_eq!(graph.neighbors(0), &[2, 1]);
+        assert_eq!(graph.weights(0), &['b', 'd']);
+        assert_eq!(graph.neighbors(1), &[] as &[usize]);
+        assert_eq!(graph.neighbors(2), &[0, 1, 2]);
+        assert_eq!(graph.weights(2), &['a', 'c', 'e']);
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid edge weight")]
+    fn test_csr_graph_negative_weight() {
+        CsrGraph::from_edges(3, vec![(0, 1, 2), (1, 2, -1)]).shortest_paths(0);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_csr_graph_serde() {
+        let mut graph = Graph::from_edges(vec![(); 4], vec![(0, 1, 2), (1, 3, 1), (0, 3, 5)]);
+        graph.remove_node(2);
+        let csr = CsrGraph::from(&graph);
+        let json = serde_json::to_string(&csr).unwrap();
+        println!("json: {}", json);
+        let parsed: CsrGraph<i32> = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, csr);
+        assert_eq!(parsed.shortest_paths(0).path_to(3), Some((3, vec![0, 1, 3])));
+
+        // the layout is checked instead of panicking in the traversals
+        let cases = [
+            (r#"{"offsets":[0,1],"targets":[0],"weights":[null],"live":[true,true],"node_count":2}"#, "invalid offsets"),
+            (r#"{"offsets":[],"targets":[],"weights":[],"live":[],"node_count":0}"#, "invalid offsets"),
+            (r#"{"offsets":[0,2],"targets":[0],"weights":[null],"live":[true],"node_count":1}"#, "invalid offsets"),
+            (r#"{"offsets":[0,2,1],"targets":[0],"weights":[null],"live":[true,true],"node_count":2}"#, "invalid offsets of the node: 1"),
+            (r#"{"offsets":[0,1],"targets":[0],"weights":[],"live":[true],"node_count":1}"#, "invalid number of weights: 0"),
+            (r#"{"offsets":[0,1],"targets":[0],"weights":[null],"live":[true],"node_count":2}"#, "invalid node count: 2"),
+            (r#"{"offsets":[0,1],"targets":[1],"weights":[null],"live":[true],"node_count":1}"#, "invalid node: 1"),
+            (r#"{"offsets":[0,1,1],"targets":[1],"weights":[null],"live":[true,false],"node_count":1}"#, "invalid node: 1"),
+            (r#"{"offsets":[0,0,1],"targets":[0],"weights":[null],"live":[true,false],"node_count":1}"#, "invalid offsets of the node: 1"),
+        ];
+        for (json, expected) in cases {
+            let error = serde_json::from_str::<CsrGraph>(json).unwrap_err();
+            assert!(error.to_string().starts_with(expected), "{}: {}", json, error);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid node")]
+    fn test_csr_graph_invalid_node() {
+        CsrGraph::new(2, vec![(2, 0)]);
+    }
+}
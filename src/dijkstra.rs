@@ -1,20 +1,53 @@
-use std::{collections::HashSet, vec};
 use std::fmt::Debug;
+use crate::error::GraphError;
+use crate::graph::Graph;
 use crate::priority_queue::PriorityQueue;
+use crate::visit;
+use crate::weight::Weight;
 
 /// Dijkstra algorithm
 ///
-/// This crate implements a Dijkstra algorithm to compute the shortest path by given graph.
+/// This crate implements a Dijkstra algorithm to compute the shortest path by given graph,
+/// the edge weights can be any integer or float primitive implementing `Weight`.
+/// The graph is a `Graph` whose edge payloads are the weights.
 /// 
 #[derive(Debug)]
-pub struct Dijkstra {
-    adjacent_list: Vec<Vec<(usize, usize)>>,
-    num_nodes: usize,
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "DijkstraData<W>", bound(deserialize = "W: Weight + serde::Deserialize<'de>"))
+)]
+pub struct Dijkstra<W = usize> {
+    graph: Graph<(), W>,
 }
 
-impl Dijkstra {
+// the unchecked deserialized graph, whose weights are checked as by `try_add_edge`
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+#[serde(rename = "Dijkstra")]
+struct DijkstraData<W> {
+    graph: Graph<(), W>,
+}
+
+#[cfg(feature = "serde")]
+impl<W: Weight> TryFrom<DijkstraData<W>> for Dijkstra<W> {
+    type Error = GraphError;
+
+    fn try_from(data: DijkstraData<W>) -> Result<Self, GraphError> {
+        for (_, edge) in data.graph.edges() {
+            _check_weight(*edge.weight())?;
+        }
+        Ok(Dijkstra { graph: data.graph })
+    }
+}
+
+impl<W: Weight> Dijkstra<W> {
     /// Create a new Dijkstra graph with edges tuple(current, neighbor, weight) Vec
     /// 
+    /// # Panics
+    /// 
+    /// Panics if a weight is negative or NaN.
+    /// 
     /// # Example
     /// 
     /// ```
@@ -23,21 +56,108 @@ impl Dijkstra {
     /// let times = vec![(0, 1, 9), (0, 3, 2), (1, 4, 1), (3, 1, 4), (3, 4, 6), (2, 1, 3), (4, 2, 7), (2, 0, 5)];
     /// let dijkstra = Dijkstra::new(5, times);
     /// 
+    /// let latencies = vec![(0, 1, 0.5), (1, 2, 1.25)];
+    /// let dijkstra = Dijkstra::new(3, latencies);
+    /// 
     /// ```
-    pub fn new(num_nodes: usize, edges: Vec<(usize, usize, usize)>) -> Self {
-        let mut adjacent_list = vec![Vec::new(); num_nodes];
-        for edge in edges {
-            let source = edge.0;
-            let target = edge.1;
-            adjacent_list[source].push((target, edge.2));
-        }
-        Dijkstra {
-            adjacent_list,
-            num_nodes,
+    pub fn new(num_nodes: usize, edges: Vec<(usize, usize, W)>) -> Self {
+        Dijkstra::from(Graph::from_edges(vec![(); num_nodes], edges))
+    }
+
+    /// Create a new Dijkstra graph with edges tuple(current, neighbor, weight) Vec,
+    /// or an error if an edge refers to a node out of range or if a weight is negative or NaN
+    /// 
+    /// # Example
+    /// 
+    /// ```
+    /// use flex_algo::{Dijkstra, GraphError};
+    /// 
+    /// let dijkstra = Dijkstra::try_new(3, vec![(0, 1, 0.5), (1, 2, 1.25)]).unwrap();
+    /// assert_eq!(dijkstra.graph().edge_count(), 2);
+    /// 
+    /// let error = Dijkstra::try_new(3, vec![(0, 1, 0.5), (1, 2, -1.25)]).unwrap_err();
+    /// assert_eq!(error, GraphError::NegativeWeight("-1.25".to_string()));
+    /// assert!(Dijkstra::try_new(3, vec![(0, 1, f64::NAN)]).is_err());
+    /// 
+    /// ```
+    pub fn try_new(num_nodes: usize, edges: Vec<(usize, usize, W)>) -> Result<Self, GraphError> {
+        let mut dijkstra = Dijkstra {
+            graph: Graph::from_edges(vec![(); num_nodes], Vec::new()),
+        };
+        for (source, target, weight) in edges {
+            dijkstra.try_add_edge(source, target, weight)?;
         }
+        Ok(dijkstra)
+    }
+
+    /// Return the weighted graph
+    pub fn graph(&self) -> &Graph<(), W> {
+        &self.graph
     }
 
-    /// Return the shortest path
+    /// Add a node and return its id
+    pub fn add_node(&mut self) -> usize {
+        self.graph.add_node(())
+    }
+
+    /// Add an edge from the source to the target node and return its id
+    /// 
+    /// # Panics
+    /// 
+    /// Panics if a node doesn't exist or if the weight is negative or NaN.
+    /// 
+    /// # Example
+    /// 
+    /// ```
+    /// use flex_algo::Dijkstra;
+    /// 
+    /// let mut dijkstra = Dijkstra::new(2, vec![(0, 1, 9)]);
+    /// let node = dijkstra.add_node();
+    /// dijkstra.add_edge(0, node, 2);
+    /// dijkstra.add_edge(node, 1, 3);
+    /// assert_eq!(dijkstra.shortest_paths(0).path_to(1), Some((5, vec![0, 2, 1])));
+    /// 
+    /// ```
+    pub fn add_edge(&mut self, source: usize, target: usize, weight: W) -> usize {
+        assert!(weight.is_valid() && weight >= W::zero(), "invalid edge weight: {:?}", weight);
+        self.graph.add_edge(source, target, weight)
+    }
+
+    /// Add an edge from the source to the target node and return its id,
+    /// or an error if a node doesn't exist or if the weight is negative or NaN
+    /// 
+    /// # Example
+    /// 
+    /// ```
+    /// use flex_algo::{Dijkstra, GraphError};
+    /// 
+    /// let mut dijkstra = Dijkstra::new(2, vec![(0, 1, 9)]);
+    /// assert_eq!(dijkstra.try_add_edge(1, 0, 3), Ok(1));
+    /// assert_eq!(dijkstra.try_add_edge(1, 2, 3), Err(GraphError::InvalidNode(2)));
+    /// assert_eq!(dijkstra.try_add_edge(1, 0, -3), Err(GraphError::NegativeWeight("-3".to_string())));
+    /// 
+    /// ```
+    pub fn try_add_edge(&mut self, source: usize, target: usize, weight: W) -> Result<usize, GraphError> {
+        _check_weight(weight)?;
+        self.graph.try_add_edge(source, target, weight)
+    }
+
+    /// Remove the edge and return its weight, or `None` if it doesn't exist
+    pub fn remove_edge(&mut self, edge: usize) -> Option<W> {
+        self.graph.remove_edge(edge)
+    }
+
+    /// Remove the node with all its edges, the ids of the other nodes are unchanged
+    pub fn remove_node(&mut self, node: usize) -> bool {
+        self.graph.remove_node(node).is_some()
+    }
+
+    fn _dijkstra(&self, source: usize) -> (ShortestPaths<W>, Vec<usize>) {
+        visit::_dijkstra(&self.graph, source)
+    }
+
+    /// Return the maximum distance and the visit order of the shortest paths from the node,
+    /// or `None` if any node is unreachable or its distance overflows `W`, use `shortest_paths` for partially connected graphs
     /// 
     /// # Example
     /// 
@@ -51,42 +171,371 @@ impl Dijkstra {
     /// assert_eq!(max, 14);
     /// 
     /// ```
-    pub fn shortest_path(&self, node: usize) -> Option<(usize, Vec<usize>)> {
-        let mut distances = vec![usize::MAX; self.num_nodes];
-        distances[node] = 0;
-        let distances_ptr = distances.as_mut_ptr();
-        let mut heap = PriorityQueue::new(|a: &usize,b:&usize| distances.get(*a).cloned() < distances.get(*b).cloned());
-        let mut seens = HashSet::new();
-        let mut visit = Vec::new();
-        heap.push(node);
-
-        while !heap.is_empty() {
-            let vertex = heap.pop().unwrap();
-            if !seens.contains(&vertex) {
-                visit.push(vertex);
-                seens.insert(vertex);
-                let adjacent = &self.adjacent_list[vertex];
-                for pair in adjacent {
-                    let neighbor_vertex = pair.0;
-                    let weight = pair.1;
-                    if distances[vertex] + weight < distances[neighbor_vertex] {
-                        unsafe {
-                            *distances_ptr.add(neighbor_vertex) = distances[vertex] + weight;
-                        }
-                        heap.push(neighbor_vertex);
-                    }
+    pub fn shortest_path(&self, node: usize) -> Option<(W, Vec<usize>)> {
+        let (paths, visit) = self._dijkstra(node);
+        let mut max = W::zero();
+        for node in self.graph.node_indices() {
+            let distance = paths.distance(node)?;
+            if distance > max {
+                max = distance;
+            }
+        }
+        Some((max, visit))
+    }
+
+    /// Return the maximum distance and the visit order of the shortest paths from the node
+    /// as `shortest_path`, or an error if the node doesn't exist
+    /// 
+    /// # Example
+    /// 
+    /// ```
+    /// use flex_algo::{Dijkstra, GraphError};
+    /// 
+    /// let dijkstra = Dijkstra::new(3, vec![(0, 1, 2), (1, 2, 3)]);
+    /// assert_eq!(dijkstra.try_shortest_path(0), Ok(Some((5, vec![0, 1, 2]))));
+    /// assert_eq!(dijkstra.try_shortest_path(1), Ok(None));
+    /// assert_eq!(dijkstra.try_shortest_path(3), Err(GraphError::InvalidNode(3)));
+    /// 
+    /// ```
+    pub fn try_shortest_path(&self, node: usize) -> Result<Option<(W, Vec<usize>)>, GraphError> {
+        if !self.graph.contains_node(node) {
+            return Err(GraphError::InvalidNode(node));
+        }
+        Ok(self.shortest_path(node))
+    }
+
+    /// Return the distances and the predecessor tree of the shortest paths from the source node,
+    /// the nodes whose distance overflows `W` are reported by `ShortestPaths::is_overflowed`
+    /// 
+    /// # Example
+    /// 
+    /// ```
+    /// use flex_algo::Dijkstra;
+    /// 
+    /// let times = vec![(0, 1, 9), (0, 3, 2), (1, 4, 1), (3, 1, 4), (3, 4, 6), (2, 1, 3), (4, 2, 7), (2, 0, 5)];
+    /// let dijkstra = Dijkstra::new(5, times);
+    /// let paths = dijkstra.shortest_paths(0);
+    /// assert_eq!(paths.distances(), &[Some(0), Some(6), Some(14), Some(2), Some(7)]);
+    /// assert_eq!(paths.predecessors(), &[None, Some(3), Some(4), Some(0), Some(1)]);
+    /// assert_eq!(paths.path_to(4), Some((7, vec![0, 3, 1, 4])));
+    /// 
+    /// ```
+    pub fn shortest_paths(&self, source: usize) -> ShortestPaths<W> {
+        self._dijkstra(source).0
+    }
+
+    /// Return the distances and the predecessor tree of the shortest paths from the source node,
+    /// or an error if the source node doesn't exist
+    /// 
+    /// # Example
+    /// 
+    /// ```
+    /// use flex_algo::{Dijkstra, GraphError};
+    /// 
+    /// let dijkstra = Dijkstra::new(3, vec![(0, 1, 2), (1, 2, 3)]);
+    /// assert_eq!(dijkstra.try_shortest_paths(0).unwrap().distance(2), Some(5));
+    /// assert_eq!(dijkstra.try_shortest_paths(5).unwrap_err(), GraphError::InvalidNode(5));
+    /// 
+    /// ```
+    pub fn try_shortest_paths(&self, source: usize) -> Result<ShortestPaths<W>, GraphError> {
+        if !self.graph.contains_node(source) {
+            return Err(GraphError::InvalidNode(source));
+        }
+        Ok(self.shortest_paths(source))
+    }
+
+    /// A* search from the source to the target guided by the heuristic, an estimate of the
+    /// remaining distance from a node to the target which must never overestimate it
+    /// 
+    /// A path whose distance or estimate overflows `W` is not followed, the path is `None`
+    /// when the target is only reached through such paths.
+    /// 
+    /// # Example
+    /// 
+    /// ```
+    /// use flex_algo::Dijkstra;
+    /// 
+    /// let times = vec![(0, 1, 9), (0, 3, 2), (1, 4, 1), (3, 1, 4), (3, 4, 6), (2, 1, 3), (4, 2, 7), (2, 0, 5)];
+    /// let dijkstra = Dijkstra::new(5, times);
+    /// let search = dijkstra.astar(0, 4, |_| 0);
+    /// assert_eq!(search.path(), Some((7, vec![0, 3, 1, 4])));
+    /// println!("expanded: {}", search.expanded());
+    /// 
+    /// ```
+    pub fn astar<H>(&self, source: usize, target: usize, heuristic: H) -> AStarSearch<W>
+    where
+        H: Fn(usize) -> W,
+    {
+        let mut distances = vec![None; self.graph.node_bound()];
+        let mut predecessors = vec![None; self.graph.node_bound()];
+        let mut expanded = 0;
+        let mut pushed = 1;
+        // the heap holds (estimated total, distance, node), stale entries are skipped when popped
+        let mut heap = PriorityQueue::new(|a: &(W, W, usize), b: &(W, W, usize)| a.0 < b.0);
+        distances[source] = Some(W::zero());
+        heap.push((heuristic(source), W::zero(), source));
+
+        while let Some((_, distance, vertex)) = heap.pop() {
+            if distances[vertex].is_some_and(|best| distance > best) {
+                continue;
+            }
+            if vertex == target {
+                let paths = ShortestPaths::new(source, distances, predecessors, Vec::new());
+                return AStarSearch {
+                    path: paths.path_to(target),
+                    expanded,
+                    pushed,
+                };
+            }
+            expanded += 1;
+            for edge in self.graph.out_edges(vertex) {
+                let (neighbor_vertex, weight) = (edge.target(), *edge.weight());
+                let distance = match distance.checked_add(weight) {
+                    Some(distance) => distance,
+                    None => continue,
+                };
+                if distances[neighbor_vertex].is_none_or(|current| distance < current) {
+                    let estimate = match distance.checked_add(heuristic(neighbor_vertex)) {
+                        Some(estimate) => estimate,
+                        None => continue,
+                    };
+                    distances[neighbor_vertex] = Some(distance);
+                    predecessors[neighbor_vertex] = Some(vertex);
+                    heap.push((estimate, distance, neighbor_vertex));
+                    pushed += 1;
                 }
             }
         }
-        distances.sort();
-        let max = distances.pop().unwrap();
-        if max < usize::MAX {
-            return Some((max, visit));
+        AStarSearch {
+            path: None,
+            expanded,
+            pushed,
         }
-        None
     }
 }
 
+impl<N, W: Weight> From<Graph<N, W>> for Dijkstra<W> {
+    /// Create a new Dijkstra graph from a graph whose edge payloads are the weights
+    /// 
+    /// # Panics
+    /// 
+    /// Panics if a weight is negative or NaN.
+    /// 
+    /// # Example
+    /// 
+    /// ```
+    /// use flex_algo::{Dijkstra, Graph};
+    /// 
+    /// let graph = Graph::from_edges(vec!["home", "station", "office"], vec![(0, 1, 0.5), (1, 2, 1.25), (0, 2, 2.0)]);
+    /// let dijkstra = Dijkstra::from(graph);
+    /// assert_eq!(dijkstra.shortest_paths(0).path_to(2), Some((1.75, vec![0, 1, 2])));
+    /// 
+    /// ```
+    fn from(graph: Graph<N, W>) -> Self {
+        for (_, edge) in graph.edges() {
+            let weight = *edge.weight();
+            assert!(weight.is_valid() && weight >= W::zero(), "invalid edge weight: {:?}", weight);
+        }
+        Dijkstra {
+            graph: graph.map(|_, _| (), |_, &weight| weight),
+        }
+    }
+}
+
+/// A* search
+/// 
+/// The shortest path found by `Dijkstra::astar` with the counters of the search,
+/// to compare the efficiency of heuristics.
+/// 
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AStarSearch<W = usize> {
+    path: Option<(W, Vec<usize>)>,
+    expanded: usize,
+    pushed: usize,
+}
+
+impl<W: Weight> AStarSearch<W> {
+    /// Return the cost and the nodes of the shortest path, or `None` if the target is unreachable
+    pub fn path(&self) -> Option<(W, Vec<usize>)> {
+        self.path.clone()
+    }
+
+    /// Return the number of nodes expanded, i.e. whose neighbors have been examined
+    pub fn expanded(&self) -> usize {
+        self.expanded
+    }
+
+    /// Return the number of nodes pushed into the priority queue
+    pub fn pushed(&self) -> usize {
+        self.pushed
+    }
+}
+
+/// Shortest paths
+/// 
+/// The distances and the predecessor tree of the shortest paths from a source node,
+/// unreachable nodes have no distance and no predecessor.
+/// 
+/// A node only reached through paths whose distance overflows `W` has no distance either,
+/// it is told apart from an unreachable node by `is_overflowed`.
+/// 
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ShortestPaths<W = usize> {
+    source: usize,
+    distances: Vec<Option<W>>,
+    predecessors: Vec<Option<usize>>,
+    // the sorted nodes reached only through an overflowing distance
+    #[cfg_attr(feature = "serde", serde(default))]
+    overflowed: Vec<usize>,
+}
+
+impl<W: Weight> ShortestPaths<W> {
+    pub(crate) fn new(source: usize, distances: Vec<Option<W>>, predecessors: Vec<Option<usize>>, overflowed: Vec<usize>) -> Self {
+        ShortestPaths {
+            source,
+            distances,
+            predecessors,
+            overflowed,
+        }
+    }
+
+    /// Return the source node of the shortest paths
+    pub fn source(&self) -> usize {
+        self.source
+    }
+
+    /// Return the distance from the source to every node, `None` for unreachable and overflowed nodes
+    pub fn distances(&self) -> &[Option<W>] {
+        &self.distances
+    }
+
+    /// Return the distance from the source to the target,
+    /// or `None` if the target is unreachable or its distance overflows `W`
+    pub fn distance(&self, target: usize) -> Option<W> {
+        self.distances.get(target).cloned().flatten()
+    }
+
+    /// Return true if there is a path from the source to the target, even if its distance overflows `W`
+    pub fn is_reachable(&self, target: usize) -> bool {
+        self.distance(target).is_some() || self.is_overflowed(target)
+    }
+
+    /// Return true if the target is only reached through paths whose distance overflows `W`,
+    /// it has no distance and no predecessor
+    /// 
+    /// # Example
+    /// 
+    /// ```
+    /// use flex_algo::Dijkstra;
+    /// 
+    /// let costs: Vec<(usize, usize, u8)> = vec![(0, 1, 200), (1, 2, 100), (2, 3, 1)];
+    /// let paths = Dijkstra::new(5, costs).shortest_paths(0);
+    /// assert_eq!(paths.distances(), &[Some(0), Some(200), None, None, None]);
+    /// assert_eq!(paths.overflowed(), &[2, 3]);
+    /// assert!(paths.is_reachable(3) && paths.is_overflowed(3));
+    /// assert_eq!(paths.unreachable(), vec![4]);
+    /// 
+    /// ```
+    pub fn is_overflowed(&self, target: usize) -> bool {
+        self.overflowed.binary_search(&target).is_ok()
+    }
+
+    /// Return the nodes only reached through paths whose distance overflows `W`, in increasing order
+    pub fn overflowed(&self) -> &[usize] {
+        &self.overflowed
+    }
+
+    /// Return the nodes reachable from the source, including the source itself and the overflowed nodes
+    /// 
+    /// # Example
+    /// 
+    /// ```
+    /// use flex_algo::Dijkstra;
+    /// 
+    /// let dijkstra = Dijkstra::new(5, vec![(0, 1, 9), (1, 2, 1), (3, 4, 2)]);
+    /// let paths = dijkstra.shortest_paths(0);
+    /// assert_eq!(paths.reachable(), vec![0, 1, 2]);
+    /// assert_eq!(paths.unreachable(), vec![3, 4]);
+    /// 
+    /// ```
+    pub fn reachable(&self) -> Vec<usize> {
+        (0..self.distances.len()).filter(|&node| self.is_reachable(node)).collect()
+    }
+
+    /// Return the nodes unreachable from the source
+    pub fn unreachable(&self) -> Vec<usize> {
+        (0..self.distances.len()).filter(|&node| !self.is_reachable(node)).collect()
+    }
+
+    /// Return the predecessor of every node on its shortest path from the source
+    pub fn predecessors(&self) -> &[Option<usize>] {
+        &self.predecessors
+    }
+
+    /// Return the cost and the nodes of the shortest path from the source to the target,
+    /// or `None` if the target is unreachable or its distance overflows `W`
+    /// 
+    /// # Example
+    /// 
+    /// ```
+    /// use flex_algo::Dijkstra;
+    /// 
+    /// let times = vec![(0, 1, 9), (0, 3, 2), (1, 4, 1), (3, 1, 4), (3, 4, 6), (2, 1, 3), (4, 2, 7)];
+    /// let dijkstra = Dijkstra::new(6, times);
+    /// let paths = dijkstra.shortest_paths(0);
+    /// assert_eq!(paths.path_to(2), Some((14, vec![0, 3, 1, 4, 2])));
+    /// assert_eq!(paths.path_to(0), Some((0, vec![0])));
+    /// assert_eq!(paths.path_to(5), None);
+    /// 
+    /// ```
+    pub fn path_to(&self, target: usize) -> Option<(W, Vec<usize>)> {
+        let distance = self.distance(target)?;
+        let mut path = vec![target];
+        let mut vertex = target;
+        while let Some(predecessor) = self.predecessors[vertex] {
+            path.push(predecessor);
+            vertex = predecessor;
+        }
+        path.reverse();
+        Some((distance, path))
+    }
+}
+
+// the nodes without a distance reached from the seeds, the targets of the overflowing edges,
+// through nodes without a distance, every path to them overflows on the way
+pub(crate) fn _overflowed<W, I, F>(distances: &[Option<W>], mut seeds: Vec<bool>, neighbors: F) -> Vec<usize>
+where
+    I: Iterator<Item = usize>,
+    F: Fn(usize) -> I,
+{
+    let mut stack: Vec<usize> = (0..seeds.len()).filter(|&node| seeds[node] && distances[node].is_none()).collect();
+    for &node in &stack {
+        seeds[node] = true;
+    }
+    while let Some(vertex) = stack.pop() {
+        for neighbor in neighbors(vertex) {
+            if distances[neighbor].is_none() && !seeds[neighbor] {
+                seeds[neighbor] = true;
+                stack.push(neighbor);
+            }
+        }
+    }
+    (0..seeds.len()).filter(|&node| seeds[node] && distances[node].is_none()).collect()
+}
+
+// an error if the weight is NaN or negative
+fn _check_weight<W: Weight>(weight: W) -> Result<(), GraphError> {
+    if !weight.is_valid() {
+        return Err(GraphError::InvalidWeight(format!("{:?}", weight)));
+    }
+    if weight < W::zero() {
+        return Err(GraphError::NegativeWeight(format!("{:?}", weight)));
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -102,6 +551,60 @@ mod tests {
         // panic!()
     }
 
+    #[test]
+    fn test_dijkstra_from_graph() {
+        let graph = Graph::from_edges(
+            vec!["a", "b", "c", "d"],
+            vec![(0, 1, 4u64), (0, 2, 1), (2, 1, 2), (1, 3, 1)],
+        );
+        // the same graph for the unweighted and the weighted algorithms
+        assert_eq!(graph.shortest_unweighted_path(0, 3), Some(vec![0, 1, 3]));
+        let dijkstra = Dijkstra::from(graph.clone());
+        assert_eq!(dijkstra.graph().edge_count(), 4);
+        assert_eq!(dijkstra.shortest_paths(0).path_to(3), Some((4, vec![0, 2, 1, 3])));
+        assert_eq!(graph.node(3), Some(&"d"));
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid edge weight")]
+    fn test_dijkstra_from_graph_negative_weight() {
+        let graph = Graph::from_edges(vec![(); 2], vec![(0, 1, -1)]);
+        let _dijkstra = Dijkstra::from(graph);
+    }
+
+    #[test]
+    fn test_dijkstra_mutation() {
+        let mut dijkstra = Dijkstra::new(3, vec![(0, 1, 1), (1, 2, 1), (0, 2, 5)]);
+        assert_eq!(dijkstra.shortest_paths(0).path_to(2), Some((2, vec![0, 1, 2])));
+        assert!(dijkstra.remove_node(1));
+        assert!(!dijkstra.remove_node(1));
+        assert_eq!(dijkstra.shortest_paths(0).path_to(2), Some((5, vec![0, 2])));
+        assert_eq!(dijkstra.shortest_path(0), Some((5, vec![0, 2])));
+        let node = dijkstra.add_node();
+        assert_eq!(node, 3);
+        let edge = dijkstra.add_edge(0, node, 4);
+        assert_eq!(dijkstra.shortest_paths(0).distance(node), Some(4));
+        assert_eq!(dijkstra.remove_edge(edge), Some(4));
+        assert_eq!(dijkstra.shortest_path(0), None);
+    }
+
+    #[test]
+    fn test_dijkstra_try() {
+        assert_eq!(Dijkstra::try_new(2, vec![(0, 1, 1), (1, 5, 1)]).unwrap_err(), GraphError::InvalidNode(5));
+        let error = Dijkstra::try_new(2, vec![(0, 1, f32::NAN)]).unwrap_err();
+        println!("error: {}", error);
+        assert_eq!(error, GraphError::InvalidWeight("NaN".to_string()));
+        assert_eq!(Dijkstra::try_new(2, vec![(0, 1, -2i64)]).unwrap_err().to_string(), "negative edge weight: -2");
+
+        let mut dijkstra = Dijkstra::try_new(3, vec![(0, 1, 1), (1, 2, 1)]).unwrap();
+        assert!(dijkstra.remove_node(1));
+        assert_eq!(dijkstra.try_shortest_path(1), Err(GraphError::InvalidNode(1)));
+        assert_eq!(dijkstra.try_shortest_path(2), Ok(None));
+        assert!(dijkstra.try_shortest_paths(1).is_err());
+        assert_eq!(dijkstra.try_add_edge(0, 1, 1), Err(GraphError::InvalidNode(1)));
+        assert_eq!(dijkstra.graph().edge_count(), 0);
+    }
+
     #[test]
     fn test_shortest_path() {
         let times = vec![
@@ -113,4 +616,140 @@ mod tests {
         assert_eq!(max, 14);
         // panic!();
     }
+
+    #[test]
+    fn test_shortest_paths() {
+        let times = vec![
+          (0, 1, 9), (0, 3, 2), (1, 4, 1), (3, 1, 4), (3, 4, 6), (2, 1, 3), (4, 2, 7), (2, 0, 5)
+        ];
+        let dijkstra = Dijkstra::new(6, times);
+        let paths = dijkstra.shortest_paths(2);
+        println!("shortest paths: {:?}", paths);
+        assert_eq!(paths.source(), 2);
+        assert_eq!(paths.distances(), &[Some(5), Some(3), Some(0), Some(7), Some(4), None]);
+        assert_eq!(paths.distance(1), Some(3));
+        assert_eq!(paths.distance(5), None);
+        assert_eq!(paths.reachable(), vec![0, 1, 2, 3, 4]);
+        assert_eq!(paths.unreachable(), vec![5]);
+        assert_eq!(paths.path_to(4), Some((4, vec![2, 1, 4])));
+        assert_eq!(paths.path_to(3), Some((7, vec![2, 0, 3])));
+        assert_eq!(paths.path_to(5), None);
+        assert_eq!(paths.path_to(6), None);
+    }
+
+    #[test]
+    fn test_shortest_paths_decrease_key() {
+        // the distance of node 4 is improved by every node settled before it
+        let times = vec![
+          (0, 4, 100), (0, 1, 1), (1, 4, 50), (1, 2, 1), (2, 4, 20), (2, 3, 1), (3, 4, 1)
+        ];
+        let dijkstra = Dijkstra::new(5, times);
+        let (max, visit) = dijkstra.shortest_path(0).unwrap();
+        assert_eq!(max, 4);
+        assert_eq!(visit, vec![0, 1, 2, 3, 4]);
+        assert_eq!(dijkstra.shortest_paths(0).path_to(4), Some((4, vec![0, 1, 2, 3, 4])));
+    }
+
+    // a width x height grid where every node is connected to its right and bottom neighbors
+    fn grid(width: usize, height: usize) -> Dijkstra {
+        let mut edges = Vec::new();
+        for y in 0..height {
+            for x in 0..width {
+                let node = y * width + x;
+                if x + 1 < width {
+                    edges.push((node, node + 1, 1));
+                    edges.push((node + 1, node, 1));
+                }
+                if y + 1 < height {
+                    edges.push((node, node + width, 1));
+                    edges.push((node + width, node, 1));
+                }
+            }
+        }
+        Dijkstra::new(width * height, edges)
+    }
+
+    #[test]
+    fn test_astar() {
+        let dijkstra = grid(10, 10);
+        let target = 9 * 10 + 9;
+        let manhattan = |node: usize| (9 - node % 10) + (9 - node / 10);
+
+        let blind = dijkstra.astar(0, target, |_| 0);
+        let guided = dijkstra.astar(0, target, manhattan);
+        println!("blind: {:?}, guided: {:?}", blind.expanded(), guided.expanded());
+        let (cost, path) = guided.path().unwrap();
+        assert_eq!(cost, 18);
+        assert_eq!(path.len(), 19);
+        assert_eq!(blind.path().unwrap().0, 18);
+        assert_eq!(dijkstra.shortest_paths(0).distance(target), Some(18));
+        assert!(guided.expanded() < blind.expanded());
+        assert!(guided.pushed() < blind.pushed());
+    }
+
+    #[test]
+    fn test_astar_unreachable() {
+        let times = vec![(0, 1, 9), (0, 3, 2), (1, 4, 1), (3, 1, 4)];
+        let dijkstra = Dijkstra::new(5, times);
+        let search = dijkstra.astar(0, 2, |_| 0);
+        assert_eq!(search.path(), None);
+        assert_eq!(search.expanded(), 4);
+        assert_eq!(dijkstra.astar(2, 2, |_| 0).path(), Some((0, vec![2])));
+    }
+
+    #[test]
+    fn test_shortest_paths_float() {
+        let latencies = vec![(0, 1, 0.5), (0, 2, 2.0), (1, 2, 0.75), (2, 3, 0.25)];
+        let dijkstra = Dijkstra::new(4, latencies);
+        let paths = dijkstra.shortest_paths(0);
+        assert_eq!(paths.distances(), &[Some(0.0), Some(0.5), Some(1.25), Some(1.5)]);
+        assert_eq!(paths.path_to(3), Some((1.5, vec![0, 1, 2, 3])));
+    }
+
+    #[test]
+    fn test_shortest_paths_overflow() {
+        let costs: Vec<(usize, usize, u8)> = vec![(0, 1, 200), (1, 2, 100), (0, 2, 250)];
+        let dijkstra = Dijkstra::new(4, costs);
+        let paths = dijkstra.shortest_paths(0);
+        assert_eq!(paths.distances(), &[Some(0), Some(200), Some(250), None]);
+        assert_eq!(paths.path_to(2), Some((250, vec![0, 2])));
+        assert!(paths.overflowed().is_empty());
+        assert_eq!(paths.unreachable(), vec![3]);
+
+        // 3 is only reached through 2, whose distance overflows
+        let costs: Vec<(usize, usize, u8)> = vec![(0, 1, 200), (1, 2, 100), (2, 3, 0), (3, 2, 0), (4, 3, 1)];
+        let paths = Dijkstra::new(5, costs).shortest_paths(0);
+        assert_eq!(paths.distances(), &[Some(0), Some(200), None, None, None]);
+        assert_eq!(paths.overflowed(), &[2, 3]);
+        assert!(paths.is_reachable(2) && !paths.is_reachable(4));
+        assert_eq!(paths.reachable(), vec![0, 1, 2, 3]);
+        assert_eq!(paths.path_to(3), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid edge weight")]
+    fn test_dijkstra_nan_weight() {
+        Dijkstra::new(2, vec![(0, 1, f64::NAN)]);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_dijkstra_serde() {
+        let times = vec![
+          (0, 1, 9), (0, 3, 2), (1, 4, 1), (3, 1, 4), (3, 4, 6), (2, 1, 3), (4, 2, 7), (2, 0, 5)
+        ];
+        let dijkstra = Dijkstra::new(5, times);
+        let json = serde_json::to_string(&dijkstra).unwrap();
+        let dijkstra: Dijkstra = serde_json::from_str(&json).unwrap();
+        let (max, _path) = dijkstra.shortest_path(0).unwrap();
+        assert_eq!(max, 14);
+
+        // the weights are checked as by try_add_edge
+        let json = r#"{"graph":{"node_bound":2,"edge_bound":1,"nodes":[[0,null],[1,null]],"edges":[[0,0,1,-5]]}}"#;
+        let error = serde_json::from_str::<Dijkstra<i32>>(json).unwrap_err();
+        assert!(error.to_string().starts_with("negative edge weight: -5"));
+        let json = r#"{"graph":{"node_bound":2,"edge_bound":1,"nodes":[[0,null],[1,null]],"edges":[[0,0,1,0.5]]}}"#;
+        let dijkstra: Dijkstra<f64> = serde_json::from_str(json).unwrap();
+        assert_eq!(dijkstra.shortest_paths(0).path_to(1), Some((0.5, vec![0, 1])));
+    }
 }
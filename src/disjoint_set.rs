@@ -0,0 +1,365 @@
+//! DisjointSet
+//!
+//! This data structure implements a disjoint-set (union-find) with union by rank and path compression.
+///
+/// # Example
+///
+/// ```
+/// use flex_algo::DisjointSet;
+///
+/// let mut set = DisjointSet::new(4);
+/// set.union(0, 1);
+/// set.union(2, 3);
+/// assert!(set.connected(0, 1));
+/// assert!(!set.connected(1, 2));
+/// assert_eq!(set.count(), 2);
+/// assert_eq!(set.sets().collect::<Vec<_>>(), vec![vec![0, 1], vec![2, 3]]);
+/// ```
+///
+use std::fmt::Debug;
+
+/// DisjointSet
+///
+/// This data structure implements a disjoint-set (union-find) of the elements `0..n`,
+/// every element starts in its own set.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "DisjointSetData")
+)]
+pub struct DisjointSet {
+    parents: Vec<usize>,
+    ranks: Vec<usize>,
+    // the size of the set of every representative
+    sizes: Vec<usize>,
+    count: usize,
+}
+
+// the unchecked deserialized forest, whose invariants are checked before it is used
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+#[serde(rename = "DisjointSet")]
+struct DisjointSetData {
+    parents: Vec<usize>,
+    ranks: Vec<usize>,
+    sizes: Vec<usize>,
+    count: usize,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<DisjointSetData> for DisjointSet {
+    type Error = String;
+
+    fn try_from(data: DisjointSetData) -> Result<Self, String> {
+        let n = data.parents.len();
+        if data.ranks.len() != n || data.sizes.len() != n {
+            return Err("invalid number of ranks or sizes".to_string());
+        }
+        // the rank grows strictly from a child to its parent, so the parents can't form a cycle
+        for (element, &parent) in data.parents.iter().enumerate() {
+            if parent >= n || (parent != element && data.ranks[parent] <= data.ranks[element]) {
+                return Err(format!("invalid parent of the element: {}", element));
+            }
+        }
+        let set = DisjointSet {
+            parents: data.parents,
+            ranks: data.ranks,
+            sizes: data.sizes,
+            count: data.count,
+        };
+        // the root of every element, each path is walked once even for a crafted long chain
+        let mut roots: Vec<Option<usize>> = vec![None; n];
+        let mut sizes = vec![0; n];
+        for element in 0..n {
+            let mut path = Vec::new();
+            let mut current = element;
+            let root = loop {
+                if let Some(root) = roots[current] {
+                    break root;
+                }
+                if set.parents[current] == current {
+                    break current;
+                }
+                path.push(current);
+                current = set.parents[current];
+            };
+            roots[current] = Some(root);
+            for node in path {
+                roots[node] = Some(root);
+            }
+            sizes[root] += 1;
+        }
+        if let Some(root) = (0..n).find(|&root| set.parents[root] == root && set.sizes[root] != sizes[root]) {
+            return Err(format!("invalid size of the set: {}", root));
+        }
+        if set.count != (0..n).filter(|&element| set.parents[element] == element).count() {
+            return Err(format!("invalid count: {}", set.count));
+        }
+        Ok(set)
+    }
+}
+
+impl DisjointSet {
+    /// Create a new DisjointSet with `n` elements in their own set
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flex_algo::DisjointSet;
+    ///
+    /// let set = DisjointSet::new(5);
+    /// ```
+    pub fn new(n: usize) -> Self {
+        DisjointSet {
+            parents: (0..n).collect(),
+            ranks: vec![0; n],
+            sizes: vec![1; n],
+            count: n,
+        }
+    }
+
+    /// Add a new element in its own set and return it
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flex_algo::DisjointSet;
+    ///
+    /// let mut set = DisjointSet::new(2);
+    /// assert_eq!(set.add(), 2);
+    /// assert_eq!(set.len(), 3);
+    /// assert_eq!(set.count(), 3);
+    /// ```
+    pub fn add(&mut self) -> usize {
+        let element = self.parents.len();
+        self.parents.push(element);
+        self.ranks.push(0);
+        self.sizes.push(1);
+        self.count += 1;
+        element
+    }
+
+    /// Return the number of elements
+    pub fn len(&self) -> usize {
+        self.parents.len()
+    }
+
+    /// Return true if there is no element
+    pub fn is_empty(&self) -> bool {
+        self.parents.is_empty()
+    }
+
+    /// Return the number of sets
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Return the representative of the set of the element,
+    /// every element on the way is linked to it directly
+    ///
+    /// # Panics
+    ///
+    /// Panics if the element is out of range.
+    ///
+    pub fn find(&mut self, element: usize) -> usize {
+        let mut root = element;
+        while self.parents[root] != root {
+            root = self.parents[root];
+        }
+        let mut current = element;
+        while self.parents[current] != root {
+            let next = self.parents[current];
+            self.parents[current] = root;
+            current = next;
+        }
+        root
+    }
+
+    /// Merge the sets of the two elements, return false if they were already in the same set
+    ///
+    /// # Panics
+    ///
+    /// Panics if an element is out of range.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flex_algo::DisjointSet;
+    ///
+    /// let mut set = DisjointSet::new(3);
+    /// assert!(set.union(0, 1));
+    /// assert!(!set.union(1, 0));
+    /// ```
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let a = self.find(a);
+        let b = self.find(b);
+        if a == b {
+            return false;
+        }
+        // the shallower tree goes under the deeper one
+        let (root, child) = if self.ranks[a] < self.ranks[b] { (b, a) } else { (a, b) };
+        if self.ranks[root] == self.ranks[child] {
+            self.ranks[root] += 1;
+        }
+        self.parents[child] = root;
+        self.sizes[root] += self.sizes[child];
+        self.count -= 1;
+        true
+    }
+
+    /// Return true if the two elements are in the same set
+    ///
+    /// # Panics
+    ///
+    /// Panics if an element is out of range.
+    ///
+    pub fn connected(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    /// Return the number of elements in the set of the element
+    ///
+    /// # Panics
+    ///
+    /// Panics if the element is out of range.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flex_algo::DisjointSet;
+    ///
+    /// let mut set = DisjointSet::new(4);
+    /// set.union(0, 1);
+    /// set.union(1, 2);
+    /// assert_eq!(set.set_size(2), 3);
+    /// assert_eq!(set.set_size(3), 1);
+    /// ```
+    pub fn set_size(&mut self, element: usize) -> usize {
+        let root = self.find(element);
+        self.sizes[root]
+    }
+
+    // the representative of the element without compressing the path
+    fn _root(&self, element: usize) -> usize {
+        let mut root = element;
+        while self.parents[root] != root {
+            root = self.parents[root];
+        }
+        root
+    }
+
+    /// Return the sets, every set sorted and the sets in the order of their smallest element
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flex_algo::DisjointSet;
+    ///
+    /// let mut set = DisjointSet::new(5);
+    /// set.union(4, 1);
+    /// set.union(3, 0);
+    /// for elements in set.sets() {
+    ///     println!("set: {:?}", elements);
+    /// }
+    /// assert_eq!(set.sets().collect::<Vec<_>>(), vec![vec![0, 3], vec![1, 4], vec![2]]);
+    /// ```
+    pub fn sets(&self) -> impl Iterator<Item = Vec<usize>> {
+        // the index of the set of every representative, in the order they are met
+        let mut index: Vec<Option<usize>> = vec![None; self.parents.len()];
+        let mut sets: Vec<Vec<usize>> = Vec::with_capacity(self.count);
+        for element in 0..self.parents.len() {
+            let root = self._root(element);
+            match index[root] {
+                Some(i) => sets[i].push(element),
+                None => {
+                    index[root] = Some(sets.len());
+                    sets.push(vec![element]);
+                }
+            }
+        }
+        sets.into_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_disjoint_set() {
+        let mut set = DisjointSet::new(6);
+        assert!(set.union(0, 1));
+        assert!(set.union(2, 3));
+        assert!(set.union(1, 3));
+        assert!(!set.union(0, 2));
+        println!("disjoint set: {:?}", set);
+        assert_eq!(set.find(3), set.find(0));
+        assert_ne!(set.find(4), set.find(0));
+        assert_ne!(set.find(4), set.find(5));
+    }
+
+    #[test]
+    fn test_disjoint_set_sizes() {
+        let mut set = DisjointSet::new(8);
+        for (a, b) in [(0, 1), (2, 3), (4, 5), (6, 7), (1, 3), (5, 7), (3, 7)] {
+            assert!(set.union(a, b));
+        }
+        assert_eq!(set.count(), 1);
+        assert_eq!(set.set_size(4), 8);
+        assert!(set.connected(0, 6));
+        let element = set.add();
+        assert_eq!(set.count(), 2);
+        assert!(!set.connected(element, 0));
+        assert_eq!(set.sets().map(|elements| elements.len()).collect::<Vec<_>>(), vec![8, 1]);
+        assert_eq!(set.len(), 9);
+        assert!(DisjointSet::new(0).is_empty());
+    }
+
+    #[test]
+    fn test_disjoint_set_deep() {
+        // path compression keeps long chains of finds cheap
+        let n = 100_000;
+        let mut set = DisjointSet::new(n);
+        for element in 1..n {
+            set.union(element - 1, element);
+        }
+        for element in 0..n {
+            assert!(set.connected(0, element));
+        }
+        assert_eq!(set.set_size(n - 1), n);
+        assert_eq!(set.count(), 1);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_disjoint_set_serde() {
+        let mut set = DisjointSet::new(4);
+        set.union(0, 2);
+        let json = serde_json::to_string(&set).unwrap();
+        let mut set: DisjointSet = serde_json::from_str(&json).unwrap();
+        assert!(set.connected(2, 0));
+        assert_eq!(set.count(), 3);
+
+        // the parents must form a forest whose sizes and count match
+        let json = r#"{"parents":[1,0],"ranks":[0,0],"sizes":[1,1],"count":0}"#;
+        let error = serde_json::from_str::<DisjointSet>(json).unwrap_err();
+        assert!(error.to_string().starts_with("invalid parent of the element: 0"));
+        let json = r#"{"parents":[0,2],"ranks":[0,0],"sizes":[1,1],"count":2}"#;
+        let error = serde_json::from_str::<DisjointSet>(json).unwrap_err();
+        assert!(error.to_string().starts_with("invalid parent of the element: 1"));
+        let json = r#"{"parents":[1,1],"ranks":[0],"sizes":[1,2],"count":1}"#;
+        let error = serde_json::from_str::<DisjointSet>(json).unwrap_err();
+        assert!(error.to_string().starts_with("invalid number of ranks or sizes"));
+        let json = r#"{"parents":[1,1,2],"ranks":[0,1,0],"sizes":[1,3,1],"count":2}"#;
+        let error = serde_json::from_str::<DisjointSet>(json).unwrap_err();
+        assert!(error.to_string().starts_with("invalid size of the set: 1"));
+        let json = r#"{"parents":[1,1,2],"ranks":[0,1,0],"sizes":[1,2,1],"count":1}"#;
+        let error = serde_json::from_str::<DisjointSet>(json).unwrap_err();
+        assert!(error.to_string().starts_with("invalid count: 1"));
+        let json = r#"{"parents":[1,1,2],"ranks":[0,1,0],"sizes":[1,2,1],"count":2}"#;
+        let mut set: DisjointSet = serde_json::from_str(json).unwrap();
+        assert_eq!(set.set_size(0), 2);
+    }
+}
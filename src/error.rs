@@ -0,0 +1,40 @@
+use std::fmt::{Display, Formatter};
+
+/// GraphError
+///
+/// The error of the `try_` constructors and queries of the graphs, returned instead of panicking
+/// on an input that doesn't describe a valid graph.
+///
+/// # Example
+///
+/// ```
+/// use flex_algo::{Dijkstra, GraphError};
+///
+/// let error = Dijkstra::try_new(2, vec![(0, 2, 1)]).unwrap_err();
+/// assert_eq!(error, GraphError::InvalidNode(2));
+/// assert_eq!(error.to_string(), "invalid node: 2");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GraphError {
+    /// The node doesn't exist
+    InvalidNode(usize),
+    /// The edge tuple(source, target) is already in the graph
+    DuplicateEdge(usize, usize),
+    /// The edge weight is negative, formatted with `Debug`
+    NegativeWeight(String),
+    /// The edge weight can't be ordered, e.g. NaN, formatted with `Debug`
+    InvalidWeight(String),
+}
+
+impl Display for GraphError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GraphError::InvalidNode(node) => write!(f, "invalid node: {}", node),
+            GraphError::DuplicateEdge(source, target) => write!(f, "duplicate edge: {} -> {}", source, target),
+            GraphError::NegativeWeight(weight) => write!(f, "negative edge weight: {}", weight),
+            GraphError::InvalidWeight(weight) => write!(f, "invalid edge weight: {}", weight),
+        }
+    }
+}
+
+impl std::error::Error for GraphError {}
@@ -1,15 +1,28 @@
-use std::fmt::Debug;
 use std::collections::HashSet;
+use std::fmt::{Debug, Display, Formatter};
+use crate::error::GraphError;
+use crate::visit::{self, GraphBase, IntoEdges, IntoNeighbors, NodeCount};
 
 /// Graph data structure
 /// 
-/// This create implements a Graph data structure
+/// This create implements a Graph data structure, every node holds a payload `N` and every edge a payload `E`,
+/// e.g. a label or a weight, the nodes and the edges are identified by the order they were added in.
 /// 
-#[derive(Debug)]
-pub struct Graph {
+/// The ids are stable: removing a node or an edge leaves a hole which is never reused,
+/// so the ids of the other nodes and edges stay valid.
+/// 
+#[derive(Debug, Clone)]
+pub struct Graph<N = (), E = ()> {
+    nodes: Vec<Option<N>>,
+    edges: Vec<Option<Edge<E>>>,
+    // the ids of the outgoing edges of every node
     adjacent_list: Vec<Vec<usize>>,
-    indegree: Vec<usize>,
+    // the ids of the incoming edges of every node
+    incoming_list: Vec<Vec<usize>>,
     num_nodes: usize,
+    // the ids given to the next node and edge, the vectors only reach the highest id in use after deserializing
+    next_node: usize,
+    next_edge: usize,
 }
 
 impl Graph {
@@ -24,170 +37,1334 @@ impl Graph {
     /// let graph = Graph::new(6, vec![(1, 0), (2, 1), (2, 5), (0, 3), (4, 3), (3, 5), (4, 5)]);
     /// println!("graph: {:?}", graph);
     /// ```
-    pub fn new(num_nodes: usize, routes: Vec<(usize, usize)>) -> Self {
-        let mut adjacent_list = vec![Vec::<usize>::new(); num_nodes];
-        let mut indegree = vec![0; num_nodes];
-        for route in routes {
-          let source = route.1;
-          let target = route.0;
-          adjacent_list[source].push(target);
-          indegree[target] += 1;
-        }
-        Graph {
-          adjacent_list,
-          indegree,
-          num_nodes,
-        }
+    pub fn new(num_nodes: usize, routes: Vec<(usize, usize)>) -> Self {
+        let edges = routes.into_iter().map(|(target, source)| (source, target, ())).collect();
+        Graph::from_edges(vec![(); num_nodes], edges)
+    }
+
+    /// Create a new graph with the given routes tuple(current, neighbor) current <--- neighbor,
+    /// or an error if a route refers to a node out of range or is repeated
+    /// 
+    /// # Example
+    /// 
+    /// ```
+    /// use flex_algo::{Graph, GraphError};
+    /// 
+    /// let graph = Graph::try_new(3, vec![(1, 0), (2, 1)]).unwrap();
+    /// assert_eq!(graph.topological_sort(), Ok(vec![0, 1, 2]));
+    /// 
+    /// assert_eq!(Graph::try_new(3, vec![(1, 0), (3, 1)]).unwrap_err(), GraphError::InvalidNode(3));
+    /// assert_eq!(Graph::try_new(3, vec![(1, 0), (1, 0)]).unwrap_err(), GraphError::DuplicateEdge(0, 1));
+    /// ```
+    pub fn try_new(num_nodes: usize, routes: Vec<(usize, usize)>) -> Result<Self, GraphError> {
+        let mut graph = Graph::from_edges(vec![(); num_nodes], Vec::new());
+        // a set instead of contains_edge, which is linear in the out-degree of a high-degree node
+        let mut seen = HashSet::with_capacity(routes.len());
+        for (target, source) in routes {
+            graph._check_node(source)?;
+            graph._check_node(target)?;
+            if !seen.insert((source, target)) {
+                return Err(GraphError::DuplicateEdge(source, target));
+            }
+            graph.add_edge(source, target, ());
+        }
+        Ok(graph)
+    }
+}
+
+impl<N, E> Default for Graph<N, E> {
+    fn default() -> Self {
+        Graph {
+            nodes: Vec::new(),
+            edges: Vec::new(),
+            adjacent_list: Vec::new(),
+            incoming_list: Vec::new(),
+            num_nodes: 0,
+            next_node: 0,
+            next_edge: 0,
+        }
+    }
+}
+
+impl<N, E> Graph<N, E> {
+    /// Create a new graph with the node payloads and the edges tuple(current, neighbor, payload) Vec,
+    /// the node ids are the indexes of the payloads and the edge ids the indexes of the edges
+    /// 
+    /// # Panics
+    /// 
+    /// Panics if an edge refers to a node out of range.
+    /// 
+    /// # Example
+    /// 
+    /// ```
+    /// use flex_algo::Graph;
+    /// 
+    /// let graph = Graph::from_edges(vec!["a", "b", "c"], vec![(0, 1, 2.5), (1, 2, 1.0)]);
+    /// assert_eq!(graph.node(1), Some(&"b"));
+    /// assert_eq!(graph.edge(0).unwrap().weight(), &2.5);
+    /// assert_eq!(graph.topological_sort(), Ok(vec![0, 1, 2]));
+    /// 
+    /// ```
+    pub fn from_edges(nodes: Vec<N>, edges: Vec<(usize, usize, E)>) -> Self {
+        let mut graph = Graph::default();
+        for node in nodes {
+            graph.add_node(node);
+        }
+        for (source, target, weight) in edges {
+            graph.add_edge(source, target, weight);
+        }
+        graph
+    }
+
+    /// Create a new graph with the node payloads and the edges tuple(current, neighbor, payload) Vec,
+    /// or an error if an edge refers to a node out of range
+    /// 
+    /// # Example
+    /// 
+    /// ```
+    /// use flex_algo::{Graph, GraphError};
+    /// 
+    /// let graph = Graph::try_from_edges(vec!["a", "b"], vec![(0, 1, 2.5)]).unwrap();
+    /// assert_eq!(graph.edge_count(), 1);
+    /// assert_eq!(Graph::try_from_edges(vec!["a"], vec![(0, 1, 2.5)]).unwrap_err(), GraphError::InvalidNode(1));
+    /// ```
+    pub fn try_from_edges(nodes: Vec<N>, edges: Vec<(usize, usize, E)>) -> Result<Self, GraphError> {
+        let mut graph = Graph::default();
+        for node in nodes {
+            graph.add_node(node);
+        }
+        for (source, target, weight) in edges {
+            graph.try_add_edge(source, target, weight)?;
+        }
+        Ok(graph)
+    }
+
+    /// Add a node with its payload and return its id
+    /// 
+    /// # Example
+    /// 
+    /// ```
+    /// use flex_algo::Graph;
+    /// 
+    /// let mut graph = Graph::default();
+    /// let a = graph.add_node("a");
+    /// let b = graph.add_node("b");
+    /// graph.add_edge(a, b, 1);
+    /// assert_eq!((a, b), (0, 1));
+    /// assert_eq!(graph.neighbors(a).collect::<Vec<_>>(), vec![b]);
+    /// 
+    /// ```
+    pub fn add_node(&mut self, node: N) -> usize {
+        let id = self.next_node;
+        self.nodes.resize_with(id, || None);
+        self.adjacent_list.resize_with(id, Vec::new);
+        self.incoming_list.resize_with(id, Vec::new);
+        self.nodes.push(Some(node));
+        self.adjacent_list.push(Vec::new());
+        self.incoming_list.push(Vec::new());
+        self.num_nodes += 1;
+        self.next_node += 1;
+        id
+    }
+
+    /// Add an edge from the source to the target node with its payload and return its id,
+    /// parallel edges and self loops are allowed
+    /// 
+    /// # Panics
+    /// 
+    /// Panics if the source or the target node doesn't exist.
+    /// 
+    /// # Example
+    /// 
+    /// ```
+    /// use flex_algo::Graph;
+    /// 
+    /// let mut graph = Graph::from_edges(vec![(); 2], vec![(0, 1, 5)]);
+    /// let edge = graph.add_edge(1, 0, 7);
+    /// assert_eq!(edge, 1);
+    /// assert!(graph.contains_edge(1, 0));
+    /// assert_eq!(graph.indegree(0), 1);
+    /// 
+    /// ```
+    pub fn add_edge(&mut self, source: usize, target: usize, weight: E) -> usize {
+        assert!(self.contains_node(source), "invalid node: {}", source);
+        assert!(self.contains_node(target), "invalid node: {}", target);
+        let id = self.next_edge;
+        self.edges.resize_with(id, || None);
+        self.edges.push(Some(Edge { source, target, weight }));
+        self.next_edge += 1;
+        self.adjacent_list[source].push(id);
+        self.incoming_list[target].push(id);
+        id
+    }
+
+    /// Add an edge from the source to the target node with its payload and return its id,
+    /// or an error if the source or the target node doesn't exist
+    /// 
+    /// # Example
+    /// 
+    /// ```
+    /// use flex_algo::{Graph, GraphError};
+    /// 
+    /// let mut graph = Graph::new(2, vec![]);
+    /// assert_eq!(graph.try_add_edge(0, 1, ()), Ok(0));
+    /// graph.remove_node(1);
+    /// assert_eq!(graph.try_add_edge(0, 1, ()), Err(GraphError::InvalidNode(1)));
+    /// ```
+    pub fn try_add_edge(&mut self, source: usize, target: usize, weight: E) -> Result<usize, GraphError> {
+        self._check_node(source)?;
+        self._check_node(target)?;
+        Ok(self.add_edge(source, target, weight))
+    }
+
+    fn _check_node(&self, node: usize) -> Result<(), GraphError> {
+        if !self.contains_node(node) {
+            return Err(GraphError::InvalidNode(node));
+        }
+        Ok(())
+    }
+
+    /// Remove the edge and return its payload, or `None` if it doesn't exist
+    /// 
+    /// # Example
+    /// 
+    /// ```
+    /// use flex_algo::Graph;
+    /// 
+    /// let mut graph = Graph::from_edges(vec![(); 3], vec![(0, 1, "x"), (1, 2, "y")]);
+    /// assert_eq!(graph.remove_edge(0), Some("x"));
+    /// assert_eq!(graph.remove_edge(0), None);
+    /// assert!(!graph.contains_edge(0, 1));
+    /// // the ids of the other edges are unchanged
+    /// assert_eq!(graph.edge(1).unwrap().weight(), &"y");
+    /// 
+    /// ```
+    pub fn remove_edge(&mut self, edge: usize) -> Option<E> {
+        let removed = self.edges.get_mut(edge)?.take()?;
+        self.adjacent_list[removed.source].retain(|&id| id != edge);
+        self.incoming_list[removed.target].retain(|&id| id != edge);
+        Some(removed.weight)
+    }
+
+    /// Remove the node with all its edges and return its payload, or `None` if it doesn't exist
+    /// 
+    /// # Example
+    /// 
+    /// ```
+    /// use flex_algo::Graph;
+    /// 
+    /// let mut graph = Graph::from_edges(vec!["a", "b", "c"], vec![(0, 1, ()), (1, 2, ()), (0, 2, ())]);
+    /// assert_eq!(graph.remove_node(1), Some("b"));
+    /// assert_eq!(graph.node_count(), 2);
+    /// assert_eq!(graph.edge_count(), 1);
+    /// assert_eq!(graph.indegree(2), 1);
+    /// // the ids of the other nodes are unchanged
+    /// assert_eq!(graph.node(2), Some(&"c"));
+    /// 
+    /// ```
+    pub fn remove_node(&mut self, node: usize) -> Option<N> {
+        let removed = self.nodes.get_mut(node)?.take()?;
+        let mut edges = std::mem::take(&mut self.adjacent_list[node]);
+        edges.append(&mut self.incoming_list[node]);
+        for edge in edges {
+            self.remove_edge(edge);
+        }
+        self.num_nodes -= 1;
+        Some(removed)
+    }
+
+    /// Return the number of nodes
+    pub fn node_count(&self) -> usize {
+        self.num_nodes
+    }
+
+    /// Return the upper bound of the node ids, which may count the removed nodes
+    pub fn node_bound(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Return the number of edges
+    pub fn edge_count(&self) -> usize {
+        self.adjacent_list.iter().map(|adjacent| adjacent.len()).sum()
+    }
+
+    /// Return true if the node exists
+    pub fn contains_node(&self, node: usize) -> bool {
+        self.nodes.get(node).is_some_and(|node| node.is_some())
+    }
+
+    /// Return true if there is an edge from the source to the target node
+    pub fn contains_edge(&self, source: usize, target: usize) -> bool {
+        self.find_edge(source, target).is_some()
+    }
+
+    /// Return the id of an edge from the source to the target node
+    pub fn find_edge(&self, source: usize, target: usize) -> Option<usize> {
+        self.adjacent_list
+            .get(source)?
+            .iter()
+            .find(|&&edge| self.edges[edge].as_ref().unwrap().target == target)
+            .cloned()
+    }
+
+    /// Return the ids of the nodes in increasing order
+    pub fn node_indices(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.nodes.len()).filter(move |&node| self.nodes[node].is_some())
+    }
+
+    /// Return the payload of the node
+    pub fn node(&self, node: usize) -> Option<&N> {
+        self.nodes.get(node)?.as_ref()
+    }
+
+    /// Return the mutable payload of the node
+    pub fn node_mut(&mut self, node: usize) -> Option<&mut N> {
+        self.nodes.get_mut(node)?.as_mut()
+    }
+
+    /// Return the ids and the payloads of the nodes
+    pub fn nodes(&self) -> impl Iterator<Item = (usize, &N)> + '_ {
+        self.nodes.iter().enumerate().filter_map(|(id, node)| Some((id, node.as_ref()?)))
+    }
+
+    /// Return the edge
+    pub fn edge(&self, edge: usize) -> Option<&Edge<E>> {
+        self.edges.get(edge)?.as_ref()
+    }
+
+    /// Return the mutable payload of the edge
+    pub fn edge_weight_mut(&mut self, edge: usize) -> Option<&mut E> {
+        self.edges.get_mut(edge)?.as_mut().map(|edge| &mut edge.weight)
+    }
+
+    /// Return the ids and the edges
+    pub fn edges(&self) -> impl Iterator<Item = (usize, &Edge<E>)> + '_ {
+        self.edges.iter().enumerate().filter_map(|(id, edge)| Some((id, edge.as_ref()?)))
+    }
+
+    /// Return the outgoing edges of the node
+    /// 
+    /// # Example
+    /// 
+    /// ```
+    /// use flex_algo::Graph;
+    /// 
+    /// let graph = Graph::from_edges(vec![(); 3], vec![(0, 1, "x"), (0, 2, "y"), (1, 2, "z")]);
+    /// let labels: Vec<&str> = graph.out_edges(0).map(|edge| *edge.weight()).collect();
+    /// assert_eq!(labels, vec!["x", "y"]);
+    /// 
+    /// ```
+    pub fn out_edges(&self, node: usize) -> impl Iterator<Item = &Edge<E>> + '_ {
+        self.adjacent_list[node].iter().map(move |&edge| self.edges[edge].as_ref().unwrap())
+    }
+
+    /// Return the incoming edges of the node
+    pub fn in_edges(&self, node: usize) -> impl Iterator<Item = &Edge<E>> + '_ {
+        self.incoming_list[node].iter().map(move |&edge| self.edges[edge].as_ref().unwrap())
+    }
+
+    /// Return the targets of the outgoing edges of the node, once per edge
+    /// 
+    /// # Example
+    /// 
+    /// ```
+    /// use flex_algo::Graph;
+    /// 
+    /// let graph = Graph::new(4, vec![(1, 0), (2, 0), (0, 3)]);
+    /// assert_eq!(graph.neighbors(0).collect::<Vec<_>>(), vec![1, 2]);
+    /// assert_eq!(graph.in_neighbors(0).collect::<Vec<_>>(), vec![3]);
+    /// assert_eq!(graph.degree(0), 3);
+    /// 
+    /// ```
+    pub fn neighbors(&self, node: usize) -> impl Iterator<Item = usize> + '_ {
+        self.out_edges(node).map(|edge| edge.target)
+    }
+
+    /// Return the sources of the incoming edges of the node, once per edge
+    pub fn in_neighbors(&self, node: usize) -> impl Iterator<Item = usize> + '_ {
+        self.in_edges(node).map(|edge| edge.source)
+    }
+
+    /// Return the number of outgoing edges of the node
+    pub fn outdegree(&self, node: usize) -> usize {
+        self.adjacent_list.get(node).map_or(0, |adjacent| adjacent.len())
+    }
+
+    /// Return the number of incoming edges of the node
+    pub fn indegree(&self, node: usize) -> usize {
+        self.incoming_list.get(node).map_or(0, |incoming| incoming.len())
+    }
+
+    /// Return the number of edges of the node, incoming and outgoing
+    pub fn degree(&self, node: usize) -> usize {
+        self.indegree(node) + self.outdegree(node)
+    }
+
+    // the ids of the outgoing edges of the node
+    pub(crate) fn out_edge_ids(&self, node: usize) -> &[usize] {
+        &self.adjacent_list[node]
+    }
+
+    // the ids of the incoming edges of the node
+    pub(crate) fn in_edge_ids(&self, node: usize) -> &[usize] {
+        &self.incoming_list[node]
+    }
+
+    // the target of the i-th outgoing edge of the node
+    fn _neighbor(&self, node: usize, i: usize) -> Option<usize> {
+        self.adjacent_list[node].get(i).map(|&edge| self.edges[edge].as_ref().unwrap().target)
+    }
+
+    /// Create a new graph with the same structure and the payloads mapped by the closures,
+    /// which take the node or edge id and its payload
+    /// 
+    /// # Example
+    /// 
+    /// ```
+    /// use flex_algo::Graph;
+    /// 
+    /// let graph = Graph::from_edges(vec!["a", "b"], vec![(0, 1, 2)]);
+    /// let graph = graph.map(|id, label| format!("{}{}", label, id), |_, &weight| weight as f64 / 2.0);
+    /// assert_eq!(graph.node(1), Some(&"b1".to_string()));
+    /// assert_eq!(graph.edge(0).unwrap().weight(), &1.0);
+    /// 
+    /// ```
+    pub fn map<N2, E2, F, G>(&self, node_map: F, edge_map: G) -> Graph<N2, E2>
+    where
+        F: Fn(usize, &N) -> N2,
+        G: Fn(usize, &E) -> E2,
+    {
+        Graph {
+            nodes: self
+                .nodes
+                .iter()
+                .enumerate()
+                .map(|(id, node)| Some(node_map(id, node.as_ref()?)))
+                .collect(),
+            edges: self
+                .edges
+                .iter()
+                .enumerate()
+                .map(|(id, edge)| {
+                    let edge = edge.as_ref()?;
+                    Some(Edge {
+                        source: edge.source,
+                        target: edge.target,
+                        weight: edge_map(id, &edge.weight),
+                    })
+                })
+                .collect(),
+            adjacent_list: self.adjacent_list.clone(),
+            incoming_list: self.incoming_list.clone(),
+            num_nodes: self.num_nodes,
+            next_node: self.next_node,
+            next_edge: self.next_edge,
+        }
+    }
+
+    /// Check if it's an acyclic graph in linear time,
+    /// `find_cycle` returns the cycle itself
+    /// 
+    /// # Example
+    /// 
+    /// ```
+    /// use flex_algo::Graph;
+    /// 
+    /// let graph = Graph::new(6, vec![(1, 0), (2, 1), (2, 5), (0, 3), (4, 3), (3, 5), (4, 5)]);
+    /// println!("graph: {:?}", graph);
+    /// assert_eq!(graph.is_acyclic_bfs(), true);
+    /// 
+    /// ```
+    pub fn is_acyclic_bfs(&self) -> bool {
+        self.find_cycle().is_none()
+    }
+
+    /// Return a cycle of the graph by depth first search in linear time, or `None` if the graph is acyclic
+    /// 
+    /// # Example
+    /// 
+    /// ```
+    /// use flex_algo::Graph;
+    /// 
+    /// // package 0 depends on 1, 1 on 2 and 2 on 0
+    /// let graph = Graph::new(4, vec![(1, 0), (2, 1), (0, 2), (3, 2)]);
+    /// let cycle = graph.find_cycle().unwrap();
+    /// assert_eq!(cycle.nodes(), &[0, 1, 2]);
+    /// assert_eq!(cycle.to_string(), "cycle: 0 -> 1 -> 2 -> 0");
+    /// 
+    /// ```
+    pub fn find_cycle(&self) -> Option<Cycle> {
+        visit::find_cycle(self)
+    }
+
+    /// Return all the elementary cycles of the graph by the Johnson algorithm,
+    /// every cycle starts from its smallest node and no node is repeated in a cycle
+    /// 
+    /// The number of elementary cycles can grow exponentially, it is meant for small graphs.
+    /// 
+    /// # Example
+    /// 
+    /// ```
+    /// use flex_algo::Graph;
+    /// 
+    /// let graph = Graph::new(3, vec![(1, 0), (0, 1), (2, 1), (0, 2), (2, 2)]);
+    /// let cycles: Vec<Vec<usize>> = graph
+    ///     .elementary_cycles()
+    ///     .iter()
+    ///     .map(|cycle| cycle.nodes().to_vec())
+    ///     .collect();
+    /// assert_eq!(cycles, vec![vec![0, 1], vec![0, 1, 2], vec![2]]);
+    /// 
+    /// ```
+    pub fn elementary_cycles(&self) -> Vec<Cycle> {
+        // parallel routes would give the same cycle twice
+        let adjacent_list: Vec<Vec<usize>> = (0..self.node_bound())
+            .map(|vertex| {
+                let mut adjacent: Vec<usize> = self.neighbors(vertex).collect();
+                adjacent.sort_unstable();
+                adjacent.dedup();
+                adjacent
+            })
+            .collect();
+
+        // the subgraph induced by the nodes not smaller than the bound
+        let mut subgraph = Graph::from_edges(
+            vec![(); self.node_bound()],
+            adjacent_list
+                .iter()
+                .enumerate()
+                .flat_map(|(vertex, adjacent)| adjacent.iter().map(move |&neighbor| (vertex, neighbor, ())))
+                .collect(),
+        );
+        for node in 0..self.node_bound() {
+            if !self.contains_node(node) {
+                subgraph.remove_node(node);
+            }
+        }
+
+        let mut cycles = Vec::new();
+        let mut bound = 0;
+        // the cycles through the smallest node of the strongly connected component with a cycle
+        // holding the smallest node of the subgraph, the nodes without any cycle left are skipped at once
+        loop {
+            let scc = subgraph.strongly_connected_components();
+            // a single node is a cycle only with a self loop
+            let least = scc
+                .components()
+                .iter()
+                .filter(|nodes| nodes.len() > 1 || subgraph.contains_edge(nodes[0], nodes[0]))
+                .min_by_key(|nodes| nodes[0]);
+            let nodes = match least {
+                Some(nodes) => nodes,
+                None => break,
+            };
+            let start = nodes[0];
+            let mut component = vec![false; self.node_bound()];
+            for &node in nodes {
+                component[node] = true;
+            }
+            let mut search = CircuitSearch {
+                start,
+                component,
+                blocked: vec![false; self.node_bound()],
+                blocked_by: vec![Vec::new(); self.node_bound()],
+                path: Vec::new(),
+                cycles: &mut cycles,
+            };
+            search.circuit(&adjacent_list);
+            for node in bound..=start {
+                subgraph.remove_node(node);
+            }
+            bound = start + 1;
+        }
+        cycles
+    }
+
+    /// Topological Sort algorithm to check if it's an acyclic graph
+    /// 
+    /// # Example
+    /// 
+    /// ```
+    /// use flex_algo::Graph;
+    /// 
+    /// let graph = Graph::new(6, vec![(1, 0), (2, 1), (2, 5), (0, 3), (4, 3), (3, 5), (4, 5)]);
+    /// println!("graph: {:?}", graph);
+    /// assert_eq!(graph.is_acyclic_top_sort(), true);
+    /// 
+    /// ```
+    pub fn is_acyclic_top_sort(&self) -> bool {
+        self.topological_sort().is_ok()
+    }
+
+    /// Return the strongly connected components by the Tarjan algorithm in linear time,
+    /// the components are numbered in the topological order of the condensation
+    /// 
+    /// # Example
+    /// 
+    /// ```
+    /// use flex_algo::Graph;
+    /// 
+    /// // 0 <-> 1 -> 2 <-> 3 -> 4
+    /// let graph = Graph::new(5, vec![(1, 0), (0, 1), (2, 1), (3, 2), (2, 3), (4, 3)]);
+    /// let scc = graph.strongly_connected_components();
+    /// assert_eq!(scc.count(), 3);
+    /// assert_eq!(scc.components(), &[vec![0, 1], vec![2, 3], vec![4]]);
+    /// assert_eq!(scc.component_of(3), Some(1));
+    /// 
+    /// ```
+    pub fn strongly_connected_components(&self) -> StronglyConnectedComponents {
+        let mut index = vec![None; self.node_bound()];
+        let mut lowlink = vec![0; self.node_bound()];
+        let mut on_stack = vec![false; self.node_bound()];
+        let mut stack = Vec::new();
+        let mut components = Vec::new();
+        let mut counter = 0;
+
+        for root in self.node_indices() {
+            if index[root].is_some() {
+                continue;
+            }
+            // iterative, every frame holds a vertex and the index of its next neighbor
+            let mut frames = vec![(root, 0)];
+            index[root] = Some(counter);
+            lowlink[root] = counter;
+            counter += 1;
+            stack.push(root);
+            on_stack[root] = true;
+
+            while let Some(frame) = frames.last_mut() {
+                let vertex = frame.0;
+                if let Some(neighbor) = self._neighbor(vertex, frame.1) {
+                    frame.1 += 1;
+                    match index[neighbor] {
+                        None => {
+                            index[neighbor] = Some(counter);
+                            lowlink[neighbor] = counter;
+                            counter += 1;
+                            stack.push(neighbor);
+                            on_stack[neighbor] = true;
+                            frames.push((neighbor, 0));
+                        }
+                        Some(neighbor_index) if on_stack[neighbor] => {
+                            lowlink[vertex] = lowlink[vertex].min(neighbor_index);
+                        }
+                        _ => {}
+                    }
+                    continue;
+                }
+                frames.pop();
+                if let Some(&(parent, _)) = frames.last() {
+                    lowlink[parent] = lowlink[parent].min(lowlink[vertex]);
+                }
+                // the vertex is the root of a component, the nodes above it on the stack
+                if Some(lowlink[vertex]) == index[vertex] {
+                    let mut component = Vec::new();
+                    loop {
+                        let node = stack.pop().unwrap();
+                        on_stack[node] = false;
+                        component.push(node);
+                        if node == vertex {
+                            break;
+                        }
+                    }
+                    component.sort_unstable();
+                    components.push(component);
+                }
+            }
+        }
+
+        // Tarjan finds the components in reverse topological order
+        components.reverse();
+        let mut component_of = vec![None; self.node_bound()];
+        for (id, component) in components.iter().enumerate() {
+            for &node in component {
+                component_of[node] = Some(id);
+            }
+        }
+        StronglyConnectedComponents {
+            component_of,
+            components,
+        }
+    }
+
+    /// Return the strongly connected components and the condensation of the graph,
+    /// an acyclic graph with a node per component and a route between two components
+    /// when there is one between their nodes
+    /// 
+    /// # Example
+    /// 
+    /// ```
+    /// use flex_algo::Graph;
+    /// 
+    /// let graph = Graph::new(5, vec![(1, 0), (0, 1), (2, 1), (3, 2), (2, 3), (4, 3), (3, 0)]);
+    /// let (scc, condensation) = graph.condensation();
+    /// assert_eq!(scc.count(), 3);
+    /// assert_eq!(condensation.topological_sort(), Ok(vec![0, 1, 2]));
+    /// 
+    /// ```
+    pub fn condensation(&self) -> (StronglyConnectedComponents, Graph) {
+        let scc = self.strongly_connected_components();
+        let mut routes = Vec::new();
+        for (_, edge) in self.edges() {
+            let source = scc.component_of[edge.source].unwrap();
+            let target = scc.component_of[edge.target].unwrap();
+            if source != target {
+                routes.push((target, source));
+            }
+        }
+        routes.sort_unstable_by_key(|&(target, source)| (source, target));
+        routes.dedup();
+        let graph = Graph::new(scc.count(), routes);
+        (scc, graph)
+    }
+
+    /// Return the topological order of the nodes by the Kahn algorithm, every node comes before its neighbors,
+    /// or a cycle when the graph is not acyclic, the graph is left intact
+    /// 
+    /// # Example
+    /// 
+    /// ```
+    /// use flex_algo::Graph;
+    /// 
+    /// let graph = Graph::new(6, vec![(1, 0), (2, 1), (2, 5), (0, 3), (4, 3), (3, 5), (4, 5)]);
+    /// assert_eq!(graph.topological_sort(), Ok(vec![5, 3, 0, 4, 1, 2]));
+    /// 
+    /// let graph = Graph::new(4, vec![(1, 0), (2, 1), (3, 2), (1, 3)]);
+    /// let cycle = graph.topological_sort().unwrap_err();
+    /// assert_eq!(cycle.nodes(), &[1, 2, 3]);
+    /// 
+    /// ```
+    pub fn topological_sort(&self) -> Result<Vec<usize>, Cycle> {
+        visit::topological_sort(self)
+    }
+
+    /// Return the topological order where the next node is always the one with the highest priority
+    /// by the comparator among the nodes whose predecessors are all sorted, or a cycle when the graph is not acyclic
+    /// 
+    /// # Example
+    /// 
+    /// ```
+    /// use flex_algo::Graph;
+    /// 
+    /// let graph = Graph::new(6, vec![(1, 0), (2, 1), (2, 5), (0, 3), (4, 3), (3, 5), (4, 5)]);
+    /// // the largest node first
+    /// let order = graph.topological_sort_by(|a: &usize, b: &usize| a > b).unwrap();
+    /// assert_eq!(order, vec![5, 3, 4, 0, 1, 2]);
+    /// 
+    /// ```
+    pub fn topological_sort_by<F>(&self, comparator: F) -> Result<Vec<usize>, Cycle>
+    where
+        F: Fn(&usize, &usize) -> bool,
+    {
+        visit::topological_sort_by(self, comparator)
+    }
+
+    /// Return the lexicographically smallest topological order, the same order for the same graph
+    /// whatever the order of its routes, or a cycle when the graph is not acyclic
+    /// 
+    /// # Example
+    /// 
+    /// ```
+    /// use flex_algo::Graph;
+    /// 
+    /// let graph = Graph::new(6, vec![(1, 0), (2, 1), (2, 5), (0, 3), (4, 3), (3, 5), (4, 5)]);
+    /// assert_eq!(graph.lexicographic_topological_sort(), Ok(vec![5, 3, 0, 1, 2, 4]));
+    /// 
+    /// ```
+    pub fn lexicographic_topological_sort(&self) -> Result<Vec<usize>, Cycle> {
+        self.topological_sort_by(|a: &usize, b: &usize| a < b)
+    }
+
+    /// Return the path by breadth first search algo for the graph
+    /// 
+    /// # Example
+    /// 
+    /// ```
+    /// use flex_algo::Graph;
+    /// 
+    /// let graph = Graph::new(6, vec![(1, 0), (2, 1), (2, 5), (0, 3), (4, 3), (3, 5), (4, 5)]);
+    /// let visit = graph.breadth_first_search(5);
+    /// assert_eq!(visit, vec![5, 2, 3, 4, 0, 1]);
+    /// 
+    /// ```
+    pub fn breadth_first_search(&self, start: usize) -> Vec<usize> {
+        self.bfs(start).order
+    }
+
+    /// Return the visit order of the breadth first search from the start node,
+    /// or an error if the start node doesn't exist
+    /// 
+    /// # Example
+    /// 
+    /// ```
+    /// use flex_algo::{Graph, GraphError};
+    /// 
+    /// let graph = Graph::new(3, vec![(1, 0), (2, 1)]);
+    /// assert_eq!(graph.try_breadth_first_search(0), Ok(vec![0, 1, 2]));
+    /// assert_eq!(graph.try_breadth_first_search(3), Err(GraphError::InvalidNode(3)));
+    /// 
+    /// ```
+    pub fn try_breadth_first_search(&self, start: usize) -> Result<Vec<usize>, GraphError> {
+        self._check_node(start)?;
+        Ok(self.breadth_first_search(start))
+    }
+
+    /// Breadth first search from the start node, return the visit order
+    /// with the level (hop distance) and the parent of every node
+    /// 
+    /// # Example
+    /// 
+    /// ```
+    /// use flex_algo::Graph;
+    /// 
+    /// let graph = Graph::new(6, vec![(1, 0), (2, 1), (2, 5), (0, 3), (4, 3), (3, 5), (4, 5)]);
+    /// let bfs = graph.bfs(5);
+    /// assert_eq!(bfs.order(), &[5, 2, 3, 4, 0, 1]);
+    /// assert_eq!(bfs.levels(), &[Some(2), Some(3), Some(1), Some(1), Some(1), Some(0)]);
+    /// assert_eq!(bfs.parent(1), Some(0));
+    /// assert_eq!(bfs.path_to(1), Some(vec![5, 3, 0, 1]));
+    /// 
+    /// ```
+    pub fn bfs(&self, start: usize) -> BreadthFirstSearch {
+        visit::bfs(self, start)
+    }
+
+    /// Return the path with the fewest edges from the source to the target, or `None` if the target is unreachable
+    /// 
+    /// # Example
+    /// 
+    /// ```
+    /// use flex_algo::Graph;
+    /// 
+    /// let graph = Graph::new(6, vec![(1, 0), (2, 1), (2, 5), (0, 3), (4, 3), (3, 5), (4, 5)]);
+    /// assert_eq!(graph.shortest_unweighted_path(3, 2), Some(vec![3, 0, 1, 2]));
+    /// assert_eq!(graph.shortest_unweighted_path(2, 3), None);
+    /// 
+    /// ```
+    pub fn shortest_unweighted_path(&self, source: usize, target: usize) -> Option<Vec<usize>> {
+        self.bfs(source).path_to(target)
+    }
+
+    /// Return the path by depth first search algo for the graph
+    /// 
+    /// # Example
+    /// 
+    /// ```
+    /// use flex_algo::Graph;
+    /// 
+    /// let graph = Graph::new(8, vec![
+    ///     (5, 4), (2, 4), (6, 4), 
+    ///     (7, 5), (0, 2), (1, 2), (3, 6)
+    /// ]);
+    /// let visit = graph.depth_first_search(4);
+    /// 
+    /// assert_eq!(visit, vec![7, 5, 0, 1, 2, 3, 6, 4]);
+    /// ```
+    pub fn depth_first_search(&self, vertex: usize) -> Vec<usize> {
+        self.dfs(vertex).postorder
+    }
+
+    /// Return the postorder of the depth first search from the node,
+    /// or an error if the node doesn't exist
+    /// 
+    /// # Example
+    /// 
+    /// ```
+    /// use flex_algo::{Graph, GraphError};
+    /// 
+    /// let graph = Graph::new(3, vec![(1, 0), (2, 1)]);
+    /// assert_eq!(graph.try_depth_first_search(0), Ok(vec![2, 1, 0]));
+    /// assert_eq!(graph.try_depth_first_search(7), Err(GraphError::InvalidNode(7)));
+    /// 
+    /// ```
+    pub fn try_depth_first_search(&self, vertex: usize) -> Result<Vec<usize>, GraphError> {
+        self._check_node(vertex)?;
+        Ok(self.depth_first_search(vertex))
     }
 
-    /// Breadth First Search algorithm to check if it's an acyclic graph,
+    /// Depth first search from the start node, safe on cycles and deep graphs,
+    /// return the preorder and postorder, the discovery/finish times and the classified edges
     /// 
     /// # Example
     /// 
     /// ```
     /// use flex_algo::Graph;
+    /// use flex_algo::graph::EdgeKind;
     /// 
-    /// let graph = Graph::new(6, vec![(1, 0), (2, 1), (2, 5), (0, 3), (4, 3), (3, 5), (4, 5)]);
-    /// println!("graph: {:?}", graph);
-    /// assert_eq!(graph.is_acyclic_bfs(), true);
+    /// let graph = Graph::new(4, vec![(1, 0), (2, 1), (0, 2), (3, 0), (3, 2)]);
+    /// let dfs = graph.dfs(0);
+    /// assert_eq!(dfs.preorder(), &[0, 1, 2, 3]);
+    /// assert_eq!(dfs.postorder(), &[3, 2, 1, 0]);
+    /// assert_eq!(dfs.discovery(2), Some(2));
+    /// assert_eq!(dfs.finish(2), Some(5));
+    /// assert_eq!(dfs.edges(), &[
+    ///     (0, 1, EdgeKind::Tree), (1, 2, EdgeKind::Tree), (2, 0, EdgeKind::Back),
+    ///     (2, 3, EdgeKind::Tree), (0, 3, EdgeKind::Forward)
+    /// ]);
     /// 
     /// ```
-    pub fn is_acyclic_bfs(&self) -> bool {
-        for v in 0..self.num_nodes {
-            // println!("start: {}", v);
-            let mut queue = Vec::new();
-            let mut seens = HashSet::new();
-            let mut visit = Vec::new();
-            visit.push(v);
-
-            let adjacent = &self.adjacent_list[v];
-            for &neighbor in adjacent {
-                queue.push(neighbor);
-            }
-
-            while queue.len() > 0 {
-                let vertex = queue.pop().unwrap();
-                if vertex == v {
-                  return false;
-                }
-
-                if !seens.contains(&vertex) {
-                    // println!("traverse: {}", vertex);
-                    visit.push(vertex);
-                    seens.insert(vertex);
-                }
-
-                let adjacent = &self.adjacent_list[vertex];
-                for neighbor in adjacent {
-                    if !seens.contains(neighbor) {
-                        queue.push(*neighbor);
-                    }
-                }
-            }
-            // println!("path: {:?}", visit);
-        }
-        true
+    pub fn dfs(&self, start: usize) -> DepthFirstSearch {
+        visit::dfs(self, start)
     }
 
-    /// Topological Sort algorithm to check if it's an acyclic graph
+    /// Depth first search of the whole graph, every undiscovered node in increasing order
+    /// starts a new tree of the depth first forest
     /// 
     /// # Example
     /// 
     /// ```
     /// use flex_algo::Graph;
+    /// use flex_algo::graph::EdgeKind;
     /// 
-    /// let mut graph = Graph::new(6, vec![(1, 0), (2, 1), (2, 5), (0, 3), (4, 3), (3, 5), (4, 5)]);
-    /// println!("graph: {:?}", graph);
-    /// assert_eq!(graph.is_acyclic_top_sort(), true);
+    /// let graph = Graph::new(3, vec![(1, 0), (1, 2)]);
+    /// let dfs = graph.dfs_all();
+    /// assert_eq!(dfs.preorder(), &[0, 1, 2]);
+    /// assert_eq!(dfs.edges(), &[(0, 1, EdgeKind::Tree), (2, 1, EdgeKind::Cross)]);
     /// 
     /// ```
-    pub fn is_acyclic_top_sort(&mut self) -> bool {
-        let mut queue = Vec::new();
-        for i in 0..self.num_nodes {
-            if self.indegree[i] == 0 {
-                queue.push(i);
+    pub fn dfs_all(&self) -> DepthFirstSearch {
+        visit::dfs_all(self)
+    }
+}
+
+impl<N, E> GraphBase for Graph<N, E> {
+    fn node_bound(&self) -> usize {
+        Graph::node_bound(self)
+    }
+
+    fn contains_node(&self, node: usize) -> bool {
+        Graph::contains_node(self, node)
+    }
+}
+
+impl<N, E> NodeCount for Graph<N, E> {
+    fn node_count(&self) -> usize {
+        Graph::node_count(self)
+    }
+}
+
+impl<N, E> IntoNeighbors for &Graph<N, E> {
+    fn neighbors(self, node: usize) -> impl Iterator<Item = usize> {
+        Graph::neighbors(self, node)
+    }
+}
+
+impl<N, E: Copy> IntoEdges for &Graph<N, E> {
+    type EdgeWeight = E;
+
+    fn edges(self, node: usize) -> impl Iterator<Item = (usize, E)> {
+        self.out_edges(node).map(|edge| (edge.target, edge.weight))
+    }
+}
+
+// the holes of the removed nodes and edges are kept by serializing the ids and the bounds,
+// a `None` payload can't be told apart from a unit payload
+#[cfg(feature = "serde")]
+impl<N: serde::Serialize, E: serde::Serialize> serde::Serialize for Graph<N, E> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let nodes: Vec<(usize, &N)> = self.nodes().collect();
+        let edges: Vec<(usize, usize, usize, &E)> = self
+            .edges()
+            .map(|(id, edge)| (id, edge.source, edge.target, &edge.weight))
+            .collect();
+        let mut state = serializer.serialize_struct("Graph", 4)?;
+        state.serialize_field("node_bound", &self.next_node)?;
+        state.serialize_field("edge_bound", &self.next_edge)?;
+        state.serialize_field("nodes", &nodes)?;
+        state.serialize_field("edges", &edges)?;
+        state.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+#[serde(rename = "Graph")]
+struct GraphData<N, E> {
+    node_bound: usize,
+    edge_bound: usize,
+    nodes: Vec<(usize, N)>,
+    edges: Vec<(usize, usize, usize, E)>,
+}
+
+#[cfg(feature = "serde")]
+impl<'de, N: serde::Deserialize<'de>, E: serde::Deserialize<'de>> serde::Deserialize<'de> for Graph<N, E> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::Error;
+
+        let data = GraphData::<N, E>::deserialize(deserializer)?;
+        // the bounds are kept so that the ids of the removed nodes and edges are never reused,
+        // the vectors only reach the highest id in use
+        if let Some(&(id, _)) = data.nodes.iter().find(|&&(id, _)| id >= data.node_bound) {
+            return Err(D::Error::custom(format!("invalid node: {}", id)));
+        }
+        if let Some(&(id, ..)) = data.edges.iter().find(|&&(id, ..)| id >= data.edge_bound) {
+            return Err(D::Error::custom(format!("invalid edge: {}", id)));
+        }
+        let node_len = data.nodes.iter().map(|&(id, _)| id + 1).max().unwrap_or(0);
+        let edge_len = data.edges.iter().map(|&(id, ..)| id + 1).max().unwrap_or(0);
+        let mut graph = Graph {
+            nodes: (0..node_len).map(|_| None).collect(),
+            edges: (0..edge_len).map(|_| None).collect(),
+            adjacent_list: vec![Vec::new(); node_len],
+            incoming_list: vec![Vec::new(); node_len],
+            num_nodes: 0,
+            next_node: data.node_bound,
+            next_edge: data.edge_bound,
+        };
+        for (id, node) in data.nodes {
+            if graph.nodes[id].is_some() {
+                return Err(D::Error::custom(format!("invalid node: {}", id)));
             }
+            graph.nodes[id] = Some(node);
+            graph.num_nodes += 1;
         }
-        let mut count = 0;
-        while !queue.is_empty() {
-            let vertex = queue.pop().unwrap();
-            count += 1;
-            let adjacent = &self.adjacent_list[vertex];
-            for &neighbor in adjacent {
-                self.indegree[neighbor] -= 1;
-                if self.indegree[neighbor] == 0 {
-                    queue.push(neighbor);
+        let mut last = None;
+        for (id, source, target, weight) in data.edges {
+            // increasing ids keep the order of the adjacent lists
+            if last.is_some_and(|last| id <= last) {
+                return Err(D::Error::custom(format!("invalid edge: {}", id)));
+            }
+            for node in [source, target] {
+                if !graph.contains_node(node) {
+                    return Err(D::Error::custom(format!("invalid node: {}", node)));
                 }
             }
+            graph.edges[id] = Some(Edge { source, target, weight });
+            graph.adjacent_list[source].push(id);
+            graph.incoming_list[target].push(id);
+            last = Some(id);
         }
-        self.num_nodes == count
+        Ok(graph)
     }
+}
 
-    /// Return the path by breadth first search algo for the graph
-    /// 
-    /// # Example
-    /// 
-    /// ```
-    /// use flex_algo::Graph;
-    /// 
-    /// let mut graph = Graph::new(6, vec![(1, 0), (2, 1), (2, 5), (0, 3), (4, 3), (3, 5), (4, 5)]);
-    /// let visit = graph.breadth_first_search(5);
-    /// assert_eq!(visit, vec![5, 4, 3, 0, 1, 2]);
-    /// 
-    /// ```
-    pub fn breadth_first_search(&self, start: usize) -> Vec<usize> {
-        let mut queue = Vec::new();
-        let mut visit = Vec::new();
-        let mut seens = HashSet::new();
-        queue.push(start);
-
-        while !queue.is_empty() {
-            // println!("queue: {:?}", queue);
-            let vertex = queue.pop().unwrap();
-            if seens.contains(&vertex) {
+/// Edge
+/// 
+/// A directed edge of the graph from the source to the target node with a payload `E`, e.g. a weight or a label.
+/// 
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Edge<E = ()> {
+    source: usize,
+    target: usize,
+    weight: E,
+}
+
+impl<E> Edge<E> {
+    /// Return the source node of the edge
+    pub fn source(&self) -> usize {
+        self.source
+    }
+
+    /// Return the target node of the edge
+    pub fn target(&self) -> usize {
+        self.target
+    }
+
+    /// Return the payload of the edge
+    pub fn weight(&self) -> &E {
+        &self.weight
+    }
+}
+
+/// Breadth first search
+/// 
+/// The visit order of a breadth first search with the level (hop distance from the start)
+/// and the parent of every node, unreachable nodes have no level and no parent.
+/// 
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BreadthFirstSearch {
+    pub(crate) start: usize,
+    pub(crate) order: Vec<usize>,
+    pub(crate) levels: Vec<Option<usize>>,
+    pub(crate) parents: Vec<Option<usize>>,
+}
+
+impl BreadthFirstSearch {
+    /// Return the start node of the search
+    pub fn start(&self) -> usize {
+        self.start
+    }
+
+    /// Return the nodes in the order they were visited
+    pub fn order(&self) -> &[usize] {
+        &self.order
+    }
+
+    /// Return the level of every node, `None` for unreachable nodes
+    pub fn levels(&self) -> &[Option<usize>] {
+        &self.levels
+    }
+
+    /// Return the level of the node, the number of edges from the start
+    pub fn level(&self, node: usize) -> Option<usize> {
+        self.levels.get(node).cloned().flatten()
+    }
+
+    /// Return the parent of every node in the breadth first search tree
+    pub fn parents(&self) -> &[Option<usize>] {
+        &self.parents
+    }
+
+    /// Return the parent of the node in the breadth first search tree
+    pub fn parent(&self, node: usize) -> Option<usize> {
+        self.parents.get(node).cloned().flatten()
+    }
+
+    /// Return the path with the fewest edges from the start to the target, or `None` if the target is unreachable
+    pub fn path_to(&self, target: usize) -> Option<Vec<usize>> {
+        self.level(target)?;
+        let mut path = vec![target];
+        let mut vertex = target;
+        while let Some(parent) = self.parents[vertex] {
+            path.push(parent);
+            vertex = parent;
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+/// Edge kind
+/// 
+/// The classification of an edge by a depth first search.
+/// 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum EdgeKind {
+    /// An edge of the depth first search tree
+    Tree,
+    /// An edge to an ancestor, it closes a cycle
+    Back,
+    /// An edge to a descendant which is not a tree edge
+    Forward,
+    /// Any other edge, between two subtrees or two trees of the forest
+    Cross,
+}
+
+/// Depth first search
+/// 
+/// The preorder and postorder of a depth first search with the discovery/finish times,
+/// the parent of every node and the classified edges, undiscovered nodes have no times and no parent.
+/// 
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DepthFirstSearch {
+    pub(crate) preorder: Vec<usize>,
+    pub(crate) postorder: Vec<usize>,
+    pub(crate) discovery: Vec<Option<usize>>,
+    pub(crate) finish: Vec<Option<usize>>,
+    pub(crate) parents: Vec<Option<usize>>,
+    pub(crate) edges: Vec<(usize, usize, EdgeKind)>,
+}
+
+impl DepthFirstSearch {
+    pub(crate) fn new(num_nodes: usize) -> Self {
+        DepthFirstSearch {
+            preorder: Vec::new(),
+            postorder: Vec::new(),
+            discovery: vec![None; num_nodes],
+            finish: vec![None; num_nodes],
+            parents: vec![None; num_nodes],
+            edges: Vec::new(),
+        }
+    }
+
+    /// Return the nodes in the order they were discovered
+    pub fn preorder(&self) -> &[usize] {
+        &self.preorder
+    }
+
+    /// Return the nodes in the order they were finished
+    pub fn postorder(&self) -> &[usize] {
+        &self.postorder
+    }
+
+    /// Return the time the node was discovered
+    pub fn discovery(&self, node: usize) -> Option<usize> {
+        self.discovery.get(node).cloned().flatten()
+    }
+
+    /// Return the time the node was finished, after all its descendants
+    pub fn finish(&self, node: usize) -> Option<usize> {
+        self.finish.get(node).cloned().flatten()
+    }
+
+    /// Return the parent of the node in the depth first search forest
+    pub fn parent(&self, node: usize) -> Option<usize> {
+        self.parents.get(node).cloned().flatten()
+    }
+
+    /// Return the examined edges tuple(source, target, kind) in the order they were examined
+    pub fn edges(&self) -> &[(usize, usize, EdgeKind)] {
+        &self.edges
+    }
+}
+
+/// Strongly connected components
+/// 
+/// The strongly connected components of a graph, the component id of every node
+/// and the sorted nodes of every component, numbered in topological order.
+/// 
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StronglyConnectedComponents {
+    component_of: Vec<Option<usize>>,
+    components: Vec<Vec<usize>>,
+}
+
+impl StronglyConnectedComponents {
+    /// Return the number of components
+    pub fn count(&self) -> usize {
+        self.components.len()
+    }
+
+    /// Return the component id of the node
+    pub fn component_of(&self, node: usize) -> Option<usize> {
+        self.component_of.get(node).cloned().flatten()
+    }
+
+    /// Return the component id of every node, `None` for the removed nodes
+    pub fn component_ids(&self) -> &[Option<usize>] {
+        &self.component_of
+    }
+
+    /// Return the nodes of every component
+    pub fn components(&self) -> &[Vec<usize>] {
+        &self.components
+    }
+}
+
+// the state of the Johnson algorithm for the cycles through the start node
+struct CircuitSearch<'a> {
+    start: usize,
+    component: Vec<bool>,
+    blocked: Vec<bool>,
+    blocked_by: Vec<Vec<usize>>,
+    path: Vec<usize>,
+    cycles: &'a mut Vec<Cycle>,
+}
+
+impl CircuitSearch<'_> {
+    fn circuit(&mut self, adjacent_list: &[Vec<usize>]) {
+        // iterative, every frame holds a vertex, the index of its next neighbor
+        // and whether a cycle was found through it
+        let mut frames = vec![(self.start, 0, false)];
+        self.path.push(self.start);
+        self.blocked[self.start] = true;
+
+        while let Some(frame) = frames.last_mut() {
+            let vertex = frame.0;
+            if let Some(&neighbor) = adjacent_list[vertex].get(frame.1) {
+                frame.1 += 1;
+                if !self.component[neighbor] {
+                    continue;
+                }
+                if neighbor == self.start {
+                    self.cycles.push(Cycle { nodes: self.path.clone() });
+                    frame.2 = true;
+                } else if !self.blocked[neighbor] {
+                    self.path.push(neighbor);
+                    self.blocked[neighbor] = true;
+                    frames.push((neighbor, 0, false));
+                }
                 continue;
             }
-            seens.insert(vertex);
-            visit.push(vertex);
-            let adjacent = &self.adjacent_list[vertex];
-            for neighbor in adjacent {
-                if !seens.contains(neighbor) {
-                    queue.push(*neighbor);
+            let (vertex, _, found) = frames.pop().unwrap();
+            if found {
+                self.unblock(vertex);
+            } else {
+                // the vertex stays blocked until one of its neighbors gets unblocked
+                for &neighbor in &adjacent_list[vertex] {
+                    if self.component[neighbor] && !self.blocked_by[neighbor].contains(&vertex) {
+                        self.blocked_by[neighbor].push(vertex);
+                    }
                 }
             }
+            self.path.pop();
+            if let Some(parent) = frames.last_mut() {
+                parent.2 |= found;
+            }
         }
-        visit
     }
 
-    /// Return the path by depth first search algo for the graph
-    /// 
-    /// # Example
-    /// 
-    /// ```
-    /// use flex_algo::Graph;
-    /// 
-    /// let graph = Graph::new(8, vec![
-    ///     (5, 4), (2, 4), (6, 4), 
-    ///     (7, 5), (0, 2), (1, 2), (3, 6)
-    /// ]);
-    /// let visit = graph.depth_first_search(4);
-    /// 
-    /// assert_eq!(visit, vec![7, 5, 0, 1, 2, 3, 6, 4]);
-    /// ```
-    pub fn depth_first_search(&self, vertex: usize) -> Vec<usize> {
-        let adjacent = &self.adjacent_list[vertex];
-        if adjacent.is_empty() {
-            return vec![vertex];
+    fn unblock(&mut self, vertex: usize) {
+        let mut stack = vec![vertex];
+        while let Some(vertex) = stack.pop() {
+            self.blocked[vertex] = false;
+            for node in std::mem::take(&mut self.blocked_by[vertex]) {
+                if self.blocked[node] {
+                    stack.push(node);
+                }
+            }
+        }
+    }
+}
+
+/// Cycle
+/// 
+/// The nodes of a cycle of the graph, in the order of its edges and starting from its smallest node.
+/// 
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Cycle {
+    nodes: Vec<usize>,
+}
+
+impl Cycle {
+    pub(crate) fn from_predecessors(predecessors: &[Option<usize>], node: usize) -> Self {
+        Cycle {
+            nodes: _cycle_nodes(predecessors, node),
         }
-        let mut visit = Vec::new();
-        for &neighbor in adjacent {
-            visit.append(&mut self.depth_first_search(neighbor));
+    }
+
+    // rotate the nodes to start from the smallest one
+    pub(crate) fn new(mut nodes: Vec<usize>) -> Self {
+        let smallest = (0..nodes.len()).min_by_key(|&i| nodes[i]).unwrap();
+        nodes.rotate_left(smallest);
+        Cycle { nodes }
+    }
+
+    /// Return the nodes of the cycle
+    pub fn nodes(&self) -> &[usize] {
+        &self.nodes
+    }
+}
+
+// walking back n predecessors from a node reached from a cycle ends up on the cycle itself,
+// return the nodes of the cycle in the order of its edges and starting from its smallest node
+pub(crate) fn _cycle_nodes(predecessors: &[Option<usize>], node: usize) -> Vec<usize> {
+    let mut vertex = node;
+    for _ in 0..predecessors.len() {
+        vertex = predecessors[vertex].unwrap();
+    }
+    let mut nodes = vec![vertex];
+    let mut current = predecessors[vertex].unwrap();
+    while current != vertex {
+        nodes.push(current);
+        current = predecessors[current].unwrap();
+    }
+    nodes.reverse();
+    let smallest = (0..nodes.len()).min_by_key(|&i| nodes[i]).unwrap();
+    nodes.rotate_left(smallest);
+    nodes
+}
+
+impl Display for Cycle {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "cycle: ")?;
+        for node in &self.nodes {
+            write!(f, "{} -> ", node)?;
         }
-        visit.push(vertex);
-        visit
+        write!(f, "{}", self.nodes[0])
     }
 }
 
+impl std::error::Error for Cycle {}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -200,6 +1377,114 @@ mod tests {
     }
 
     #[test]
+    fn test_generic_graph() {
+        let graph = Graph::from_edges(
+            vec!["core", "io", "net", "app"],
+            vec![(0, 1, "uses"), (0, 2, "uses"), (1, 2, "feeds"), (2, 3, "serves")],
+        );
+        println!("graph: {:?}", graph);
+        assert_eq!(graph.node_count(), 4);
+        assert_eq!(graph.edge_count(), 4);
+        assert_eq!(graph.node(3), Some(&"app"));
+        assert_eq!(graph.node(4), None);
+        let edge = graph.edge(2).unwrap();
+        assert_eq!((edge.source(), edge.target(), *edge.weight()), (1, 2, "feeds"));
+        let targets: Vec<usize> = graph.out_edges(0).map(|edge| edge.target()).collect();
+        assert_eq!(targets, vec![1, 2]);
+        assert_eq!(graph.topological_sort(), Ok(vec![0, 1, 2, 3]));
+        assert_eq!(graph.bfs(0).level(3), Some(2));
+        assert_eq!(graph.depth_first_search(0), vec![3, 2, 1, 0]);
+
+        let mut graph = graph.map(|_, name| name.len(), |id, _| id);
+        *graph.node_mut(0).unwrap() += 1;
+        *graph.edge_weight_mut(3).unwrap() *= 10;
+        assert_eq!(graph.nodes().map(|(_, &node)| node).collect::<Vec<_>>(), vec![5, 2, 3, 3]);
+        assert_eq!(graph.edges().map(|(_, edge)| *edge.weight()).collect::<Vec<_>>(), vec![0, 1, 2, 30]);
+    }
+
+    #[test]
+    fn test_graph_try() {
+        // an untrusted input is rejected instead of panicking
+        assert_eq!(Graph::try_new(2, vec![(0, 1), (2, 0)]).unwrap_err(), GraphError::InvalidNode(2));
+        assert_eq!(Graph::try_new(2, vec![(usize::MAX, 0)]).unwrap_err(), GraphError::InvalidNode(usize::MAX));
+        let error = Graph::try_new(3, vec![(1, 0), (2, 1), (1, 0)]).unwrap_err();
+        println!("error: {}", error);
+        assert_eq!(error.to_string(), "duplicate edge: 0 -> 1");
+
+        let mut graph = Graph::try_new(3, vec![(1, 0), (0, 1), (2, 2)]).unwrap();
+        assert_eq!(graph.edge_count(), 3);
+        assert_eq!(graph.try_breadth_first_search(1), Ok(vec![1, 0]));
+        assert_eq!(graph.try_depth_first_search(2), Ok(vec![2]));
+        graph.remove_node(2);
+        assert_eq!(graph.try_breadth_first_search(2), Err(GraphError::InvalidNode(2)));
+        assert_eq!(graph.try_depth_first_search(3), Err(GraphError::InvalidNode(3)));
+        assert_eq!(graph.try_add_edge(0, 2, ()), Err(GraphError::InvalidNode(2)));
+        assert_eq!(graph.edge_count(), 2);
+        assert!(Graph::<(), ()>::try_from_edges(vec![], vec![(0, 0, ())]).is_err());
+
+        // a star stays linear in the number of routes
+        let n = 200_000;
+        let mut routes: Vec<(usize, usize)> = (1..n).map(|node| (node, 0)).collect();
+        assert_eq!(Graph::try_new(n, routes.clone()).unwrap().edge_count(), n - 1);
+        routes.push((n - 1, 0));
+        assert_eq!(Graph::try_new(n, routes).unwrap_err(), GraphError::DuplicateEdge(0, n - 1));
+    }
+
+    #[test]
+    fn test_graph_mutation() {
+        let mut graph = Graph::default();
+        let nodes: Vec<usize> = ["a", "b", "c", "d"].into_iter().map(|name| graph.add_node(name)).collect();
+        assert_eq!(nodes, vec![0, 1, 2, 3]);
+        let ab = graph.add_edge(0, 1, 1);
+        graph.add_edge(1, 2, 2);
+        graph.add_edge(2, 3, 3);
+        graph.add_edge(1, 3, 4);
+        let da = graph.add_edge(3, 0, 5);
+        println!("graph: {:?}", graph);
+        assert_eq!(graph.topological_sort().unwrap_err().nodes(), &[0, 1, 2, 3]);
+        assert_eq!(graph.neighbors(1).collect::<Vec<_>>(), vec![2, 3]);
+        assert_eq!(graph.in_neighbors(3).collect::<Vec<_>>(), vec![2, 1]);
+        assert_eq!((graph.indegree(3), graph.outdegree(3), graph.degree(3)), (2, 1, 3));
+        assert_eq!(graph.find_edge(3, 0), Some(da));
+        assert!(graph.contains_edge(0, 1));
+        assert!(!graph.contains_edge(1, 0));
+
+        assert_eq!(graph.remove_edge(da), Some(5));
+        assert_eq!(graph.remove_edge(da), None);
+        assert_eq!(graph.topological_sort(), Ok(vec![0, 1, 2, 3]));
+
+        assert_eq!(graph.remove_node(2), Some("c"));
+        assert_eq!(graph.remove_node(2), None);
+        assert_eq!(graph.node_count(), 3);
+        assert_eq!(graph.node_bound(), 4);
+        assert_eq!(graph.edge_count(), 2);
+        assert_eq!(graph.node_indices().collect::<Vec<_>>(), vec![0, 1, 3]);
+        assert_eq!(graph.in_neighbors(3).collect::<Vec<_>>(), vec![1]);
+        assert_eq!(graph.indegree(3), 1);
+        assert!(!graph.contains_node(2));
+        assert_eq!(graph.topological_sort(), Ok(vec![0, 1, 3]));
+        assert_eq!(graph.bfs(0).order(), &[0, 1, 3]);
+        assert_eq!(graph.dfs_all().postorder(), &[3, 1, 0]);
+        assert_eq!(graph.strongly_connected_components().component_of(2), None);
+        assert_eq!(graph.condensation().1.node_count(), 3);
+
+        // removed ids are never reused
+        assert_eq!(graph.add_node("e"), 4);
+        assert_eq!(graph.add_edge(4, 0, 6), 5);
+        assert_eq!(graph.edge(ab).unwrap().weight(), &1);
+        assert_eq!(graph.topological_sort(), Ok(vec![4, 0, 1, 3]));
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid node: 2")]
+    fn test_graph_add_edge_removed_node() {
+        let mut graph = Graph::new(3, vec![]);
+        graph.remove_node(2);
+        graph.add_edge(0, 2, ());
+    }
+
+    #[test]
+    #[allow(clippy::bool_assert_comparison)]
     fn test_is_acyclic_graph() {
         let graph = Graph::new(6, vec![(1, 0), (2, 1), (2, 5), (0, 3), (4, 3), (3, 5), (4, 5)]);
         println!("graph: {:?}", graph);
@@ -208,10 +1493,126 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::bool_assert_comparison)]
     fn test_is_acyclic_top_sort() {
-        let mut graph = Graph::new(6, vec![(1, 0), (2, 1), (2, 5), (0, 3), (4, 3), (3, 5), (4, 5)]);
+        let graph = Graph::new(6, vec![(1, 0), (2, 1), (2, 5), (0, 3), (4, 3), (3, 5), (4, 5)]);
         println!("graph: {:?}", graph);
         assert_eq!(graph.is_acyclic_top_sort(), true);
+        // the graph is left intact
+        assert!(graph.is_acyclic_top_sort());
+    }
+
+    #[test]
+    fn test_find_cycle() {
+        let graph = Graph::new(6, vec![(1, 0), (2, 1), (2, 5), (0, 3), (4, 3), (3, 5), (4, 5)]);
+        assert_eq!(graph.find_cycle(), None);
+
+        // 0 -> 1 -> 2 -> 3 -> 1 and 4 -> 4
+        let graph = Graph::new(5, vec![(1, 0), (2, 1), (3, 2), (1, 3), (4, 4)]);
+        let cycle = graph.find_cycle().unwrap();
+        println!("{}", cycle);
+        assert_eq!(cycle.nodes(), &[1, 2, 3]);
+        assert!(!graph.is_acyclic_bfs());
+
+        let graph = Graph::new(2, vec![(1, 1)]);
+        assert_eq!(graph.find_cycle().unwrap().nodes(), &[1]);
+    }
+
+    #[test]
+    fn test_find_cycle_deep() {
+        let num_nodes = 200_000;
+        let mut routes: Vec<(usize, usize)> = (1..num_nodes).map(|node| (node, node - 1)).collect();
+        routes.push((0, num_nodes - 1));
+        let graph = Graph::new(num_nodes, routes);
+        assert_eq!(graph.find_cycle().unwrap().nodes().len(), num_nodes);
+    }
+
+    #[test]
+    fn test_elementary_cycles() {
+        // the complete graph on 3 nodes has 3 cycles of 2 nodes and 2 of 3 nodes
+        let routes = vec![(1, 0), (2, 0), (0, 1), (2, 1), (0, 2), (1, 2), (1, 2)];
+        let graph = Graph::new(4, routes);
+        let cycles: Vec<Vec<usize>> = graph.elementary_cycles().iter().map(|cycle| cycle.nodes().to_vec()).collect();
+        println!("cycles: {:?}", cycles);
+        assert_eq!(cycles, vec![vec![0, 1], vec![0, 1, 2], vec![0, 2], vec![0, 2, 1], vec![1, 2]]);
+
+        let graph = Graph::new(6, vec![(1, 0), (2, 1), (2, 5), (0, 3), (4, 3), (3, 5), (4, 5)]);
+        assert!(graph.elementary_cycles().is_empty());
+    }
+
+    #[test]
+    fn test_elementary_cycles_deep() {
+        let num_nodes = 200_000;
+        let mut routes: Vec<(usize, usize)> = (1..num_nodes).map(|node| (node, node - 1)).collect();
+        routes.push((0, num_nodes - 1));
+        let graph = Graph::new(num_nodes, routes);
+        let cycles = graph.elementary_cycles();
+        assert_eq!(cycles.len(), 1);
+        assert_eq!(cycles[0].nodes().len(), num_nodes);
+        assert_eq!(cycles[0].nodes()[num_nodes - 1], num_nodes - 1);
+    }
+
+    #[test]
+    fn test_strongly_connected_components() {
+        let routes = vec![
+            (1, 0), (2, 1), (0, 2), (3, 1), (4, 3), (5, 4), (3, 5), (6, 5), (7, 6), (6, 7), (7, 2)
+        ];
+        let graph = Graph::new(9, routes);
+        let scc = graph.strongly_connected_components();
+        println!("scc: {:?}", scc);
+        assert_eq!(scc.count(), 4);
+        assert_eq!(scc.components(), &[vec![8], vec![0, 1, 2], vec![3, 4, 5], vec![6, 7]]);
+        assert_eq!(scc.component_ids(), &[Some(1), Some(1), Some(1), Some(2), Some(2), Some(2), Some(3), Some(3), Some(0)]);
+        assert_eq!(scc.component_of(9), None);
+
+        let (scc, condensation) = graph.condensation();
+        println!("condensation: {:?}", condensation);
+        let adjacent_list: Vec<Vec<usize>> = (0..4).map(|node| condensation.neighbors(node).collect()).collect();
+        assert_eq!(adjacent_list, vec![vec![], vec![2, 3], vec![3], vec![]]);
+        assert_eq!(condensation.node_indices().map(|node| condensation.indegree(node)).collect::<Vec<_>>(), vec![0, 0, 1, 2]);
+        // the components are numbered in topological order
+        assert_eq!(condensation.lexicographic_topological_sort(), Ok((0..scc.count()).collect()));
+    }
+
+    #[test]
+    fn test_strongly_connected_components_deep() {
+        let num_nodes = 200_000;
+        let mut routes: Vec<(usize, usize)> = (1..num_nodes).map(|node| (node, node - 1)).collect();
+        routes.push((0, num_nodes - 1));
+        let graph = Graph::new(num_nodes, routes);
+        assert_eq!(graph.strongly_connected_components().count(), 1);
+    }
+
+    #[test]
+    fn test_topological_sort() {
+        let graph = Graph::new(6, vec![(1, 0), (2, 1), (2, 5), (0, 3), (4, 3), (3, 5), (4, 5)]);
+        let order = graph.topological_sort().unwrap();
+        println!("topological sort: {:?}", order);
+        let mut position = [0; 6];
+        for (i, &node) in order.iter().enumerate() {
+            position[node] = i;
+        }
+        for (target, source) in [(1, 0), (2, 1), (2, 5), (0, 3), (4, 3), (3, 5), (4, 5)] {
+            assert!(position[source] < position[target]);
+        }
+        assert_eq!(graph.topological_sort(), Ok(order));
+        assert_eq!(graph.lexicographic_topological_sort(), Ok(vec![5, 3, 0, 1, 2, 4]));
+        assert_eq!(graph.topological_sort_by(|a: &usize, b: &usize| a > b), Ok(vec![5, 3, 4, 0, 1, 2]));
+    }
+
+    #[test]
+    fn test_topological_sort_cycle() {
+        // 0 -> 1 -> 2 -> 3 -> 1, 3 -> 4 and the self loop 5 -> 5
+        let graph = Graph::new(6, vec![(1, 0), (2, 1), (3, 2), (1, 3), (4, 3), (5, 5)]);
+        let cycle = graph.topological_sort().unwrap_err();
+        println!("{}", cycle);
+        assert_eq!(cycle.nodes(), &[1, 2, 3]);
+        assert_eq!(cycle.to_string(), "cycle: 1 -> 2 -> 3 -> 1");
+        assert_eq!(graph.lexicographic_topological_sort(), Err(cycle));
+        assert!(!graph.is_acyclic_top_sort());
+
+        let graph = Graph::new(2, vec![(1, 0), (1, 1)]);
+        assert_eq!(graph.topological_sort().unwrap_err().nodes(), &[1]);
     }
 
     #[test]
@@ -220,7 +1621,23 @@ mod tests {
         let visit = graph.breadth_first_search(5);
         println!("graph: {:?}", graph);
         println!("bfs: {:?}", visit);
-        assert_eq!(visit, vec![5, 4, 3, 0, 1, 2]);
+        assert_eq!(visit, vec![5, 2, 3, 4, 0, 1]);
+    }
+
+    #[test]
+    fn test_bfs_levels() {
+        let graph = Graph::new(7, vec![(1, 0), (2, 0), (3, 1), (3, 2), (4, 3), (0, 4), (6, 5)]);
+        let bfs = graph.bfs(0);
+        println!("bfs: {:?}", bfs);
+        assert_eq!(bfs.start(), 0);
+        assert_eq!(bfs.order(), &[0, 1, 2, 3, 4]);
+        assert_eq!(bfs.levels(), &[Some(0), Some(1), Some(1), Some(2), Some(3), None, None]);
+        assert_eq!(bfs.parents(), &[None, Some(0), Some(0), Some(1), Some(3), None, None]);
+        assert_eq!(bfs.path_to(4), Some(vec![0, 1, 3, 4]));
+        assert_eq!(bfs.path_to(6), None);
+        assert_eq!(graph.shortest_unweighted_path(4, 3), Some(vec![4, 0, 1, 3]));
+        assert_eq!(graph.shortest_unweighted_path(5, 6), Some(vec![5, 6]));
+        assert_eq!(graph.shortest_unweighted_path(6, 6), Some(vec![6]));
     }
 
     #[test]
@@ -234,4 +1651,122 @@ mod tests {
         println!("dfs: {:?}", visit);
         assert_eq!(visit, vec![7, 5, 0, 1, 2, 3, 6, 4]);
     }
+
+    #[test]
+    fn test_dfs_cycle() {
+        // 0 -> 1 -> 2 -> 0 and the shared descendant 3 of 1 and 2
+        let graph = Graph::new(4, vec![(1, 0), (2, 1), (0, 2), (3, 1), (3, 2)]);
+        let visit = graph.depth_first_search(0);
+        assert_eq!(visit, vec![3, 2, 1, 0]);
+
+        let dfs = graph.dfs(0);
+        println!("dfs: {:?}", dfs);
+        assert_eq!(dfs.preorder(), &[0, 1, 2, 3]);
+        assert_eq!(dfs.discovery(3), Some(3));
+        assert_eq!(dfs.finish(3), Some(4));
+        assert_eq!(dfs.finish(0), Some(7));
+        assert_eq!(dfs.parent(3), Some(2));
+        assert_eq!(dfs.edges(), &[
+            (0, 1, EdgeKind::Tree), (1, 2, EdgeKind::Tree), (2, 0, EdgeKind::Back),
+            (2, 3, EdgeKind::Tree), (1, 3, EdgeKind::Forward)
+        ]);
+    }
+
+    #[test]
+    fn test_dfs_deep() {
+        let num_nodes = 200_000;
+        let routes = (1..num_nodes).map(|node| (node, node - 1)).collect();
+        let graph = Graph::new(num_nodes, routes);
+        let visit = graph.depth_first_search(0);
+        assert_eq!(visit.len(), num_nodes);
+        assert_eq!(visit[0], num_nodes - 1);
+    }
+
+    #[test]
+    fn test_dfs_all() {
+        let graph = Graph::new(5, vec![(1, 0), (2, 1), (1, 3), (4, 3)]);
+        let dfs = graph.dfs_all();
+        assert_eq!(dfs.preorder(), &[0, 1, 2, 3, 4]);
+        assert_eq!(dfs.postorder(), &[2, 1, 0, 4, 3]);
+        assert_eq!(dfs.parent(3), None);
+        assert_eq!(dfs.edges(), &[
+            (0, 1, EdgeKind::Tree), (1, 2, EdgeKind::Tree), (3, 1, EdgeKind::Cross), (3, 4, EdgeKind::Tree)
+        ]);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_graph_serde() {
+        let graph = Graph::new(6, vec![(1, 0), (2, 1), (2, 5), (0, 3), (4, 3), (3, 5), (4, 5)]);
+        let json = serde_json::to_string(&graph).unwrap();
+        let graph: Graph = serde_json::from_str(&json).unwrap();
+        assert_eq!(graph.breadth_first_search(5), vec![5, 2, 3, 4, 0, 1]);
+        assert!(graph.is_acyclic_bfs());
+
+        let graph = Graph::from_edges(vec!["a", "b"], vec![(0, 1, 1.5)]);
+        let json = serde_json::to_string(&graph).unwrap();
+        let graph: Graph<String, f64> = serde_json::from_str(&json).unwrap();
+        assert_eq!(graph.node(0), Some(&"a".to_string()));
+        assert_eq!(graph.edge(0).unwrap().weight(), &1.5);
+
+        // the ids are kept after removals
+        let mut graph = Graph::new(4, vec![(1, 0), (2, 1), (3, 2), (3, 0)]);
+        graph.remove_node(1);
+        let json = serde_json::to_string(&graph).unwrap();
+        println!("json: {}", json);
+        let mut graph: Graph = serde_json::from_str(&json).unwrap();
+        assert_eq!(graph.node_indices().collect::<Vec<_>>(), vec![0, 2, 3]);
+        assert_eq!(graph.find_edge(2, 3), Some(2));
+        assert_eq!(graph.in_neighbors(3).collect::<Vec<_>>(), vec![2, 0]);
+        assert_eq!(graph.add_edge(0, 2, ()), 4);
+
+        let json = r#"{"node_bound":1,"edge_bound":1,"nodes":[[0,null]],"edges":[[0,0,1,null]]}"#;
+        let error = serde_json::from_str::<Graph>(json).unwrap_err();
+        assert!(error.to_string().starts_with("invalid node: 1"));
+
+        // the removed last ids are not reused after a round trip
+        let mut graph = Graph::from_edges(vec!["a", "b", "c"], vec![(0, 1, 1), (1, 2, 2)]);
+        graph.remove_node(2);
+        let json = serde_json::to_string(&graph).unwrap();
+        let mut graph: Graph<String, i32> = serde_json::from_str(&json).unwrap();
+        assert_eq!(graph.node_bound(), 2);
+        assert_eq!(graph.add_node("d".to_string()), 3);
+        assert_eq!(graph.add_edge(0, 3, 3), 2);
+        assert_eq!(graph.node_bound(), 4);
+
+        // the removed ids don't take any room, however many there are
+        let mut graph = Graph::from_edges(vec![()], vec![(0, 0, ()); 5000]);
+        for edge in 0..5000 {
+            graph.remove_edge(edge);
+        }
+        let json = serde_json::to_string(&graph).unwrap();
+        assert_eq!(json, r#"{"node_bound":1,"edge_bound":5000,"nodes":[[0,null]],"edges":[]}"#);
+        let mut graph: Graph = serde_json::from_str(&json).unwrap();
+        assert_eq!(graph.edge_count(), 0);
+        assert_eq!(graph.add_edge(0, 0, ()), 5000);
+        assert_eq!(graph.edge(5000).unwrap().target(), 0);
+        let json = r#"{"node_bound":1000000000001,"edge_bound":1000000000000000000,"nodes":[[0,null]],"edges":[]}"#;
+        let graph: Graph = serde_json::from_str(json).unwrap();
+        assert_eq!(graph.node_bound(), 1);
+        assert_eq!(graph.node_count(), 1);
+        let json = r#"{"node_bound":6,"edge_bound":8,"nodes":[[2,null],[5,null]],"edges":[[7,5,2,null]]}"#;
+        let graph: Graph = serde_json::from_str(json).unwrap();
+        assert_eq!(graph.node_bound(), 6);
+        assert_eq!(graph.node_indices().collect::<Vec<_>>(), vec![2, 5]);
+        assert_eq!(graph.find_edge(5, 2), Some(7));
+
+        // an id beyond the bound or given twice is rejected
+        let json = r#"{"node_bound":2,"edge_bound":0,"nodes":[[2,null]],"edges":[]}"#;
+        let error = serde_json::from_str::<Graph>(json).unwrap_err();
+        assert!(error.to_string().starts_with("invalid node: 2"));
+        let json = r#"{"node_bound":1,"edge_bound":0,"nodes":[[0,null],[0,null]],"edges":[]}"#;
+        let error = serde_json::from_str::<Graph>(json).unwrap_err();
+        assert!(error.to_string().starts_with("invalid node: 0"));
+        let json = r#"{"node_bound":1,"edge_bound":9,"nodes":[[0,null]],"edges":[[5,0,0,null],[3,0,0,null]]}"#;
+        let error = serde_json::from_str::<Graph>(json).unwrap_err();
+        assert!(error.to_string().starts_with("invalid edge: 3"));
+        let json = r#"{"node_bound":1,"edge_bound":1,"nodes":[[0,null]],"edges":[[1,0,0,null]]}"#;
+        let error = serde_json::from_str::<Graph>(json).unwrap_err();
+        assert!(error.to_string().starts_with("invalid edge: 1"));
+    }
 }
@@ -0,0 +1,509 @@
+//! IndexedPriorityQueue
+//!
+//! This data structure implements an indexed Priority Queue with a comparator function to specify the Min/Max heap.
+//! Items are `usize` indexes, their priorities are stored in a Vec and the position of every item
+//! inside the heap is tracked, so the priority of an item can be changed or the item removed in O(log n).
+///
+/// # Example
+///
+/// ```
+/// use flex_algo::IndexedPriorityQueue;
+///
+/// let mut pq = IndexedPriorityQueue::new(|a: &usize, b: &usize| a < b);
+/// pq.push(0, 14);
+/// pq.push(1, 10);
+/// pq.push(2, 12);
+/// pq.decrease_key(0, 3);
+///
+/// assert_eq!(pq.pop(), Some((0, 3)));
+/// ```
+///
+use std::fmt::Debug;
+
+/// IndexedPriorityQueue
+///
+/// This data structure implements an indexed Priority Queue with a comparator function to specify the Min/Max heap.
+/// The queue is implemented as a heap of indexes, each index keeps track of its position inside the heap.
+///
+#[derive(Debug)]
+pub struct IndexedPriorityQueue<F, P>
+where
+    F: Fn(&P, &P) -> bool,
+{
+    heap: Vec<usize>,
+    positions: Vec<Option<usize>>,
+    priorities: Vec<Option<P>>,
+    comparator: F,
+}
+
+impl<F, P> IndexedPriorityQueue<F, P>
+where
+    F: Fn(&P, &P) -> bool,
+{
+    /// Create a new IndexedPriorityQueue with a comparator function
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flex_algo::IndexedPriorityQueue;
+    ///
+    /// let mut pq = IndexedPriorityQueue::new(|a: &usize, b: &usize| a < b);
+    ///
+    /// ```
+    pub fn new(comparator: F) -> Self {
+        IndexedPriorityQueue {
+            heap: Vec::new(),
+            positions: Vec::new(),
+            priorities: Vec::new(),
+            comparator,
+        }
+    }
+
+    /// Create a new IndexedPriorityQueue with a comparator function from (item, priority) pairs,
+    /// e.g. the heap contents of a deserialized IndexedPriorityQueue
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flex_algo::IndexedPriorityQueue;
+    ///
+    /// let mut pq = IndexedPriorityQueue::from_vec(|a: &usize, b: &usize| a < b, vec![(0, 14), (1, 10)]);
+    /// assert_eq!(pq.pop(), Some((1, 10)));
+    /// ```
+    pub fn from_vec(comparator: F, elements: Vec<(usize, P)>) -> Self {
+        let mut pq = IndexedPriorityQueue::new(comparator);
+        for (item, priority) in elements {
+            pq.push(item, priority);
+        }
+        pq
+    }
+
+    /// Return the size of the IndexedPriorityQueue
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flex_algo::IndexedPriorityQueue;
+    ///
+    /// let mut pq = IndexedPriorityQueue::new(|a: &usize, b: &usize| a < b);
+    /// assert_eq!(pq.size(), 0);
+    /// ```
+    pub fn size(&self) -> usize {
+        self.heap.len()
+    }
+
+    /// Return true if the IndexedPriorityQueue is empty
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flex_algo::IndexedPriorityQueue;
+    ///
+    /// let mut pq = IndexedPriorityQueue::new(|a: &usize, b: &usize| a < b);
+    /// assert_eq!(pq.is_empty(), true);
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    /// Return true if the item is in the IndexedPriorityQueue
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flex_algo::IndexedPriorityQueue;
+    ///
+    /// let mut pq = IndexedPriorityQueue::new(|a: &usize, b: &usize| a < b);
+    /// pq.push(3, 10);
+    /// assert_eq!(pq.contains(3), true);
+    /// assert_eq!(pq.contains(1), false);
+    /// ```
+    pub fn contains(&self, item: usize) -> bool {
+        matches!(self.positions.get(item), Some(Some(_)))
+    }
+
+    /// Return the priority of the item, or `None` if it is not in the queue
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flex_algo::IndexedPriorityQueue;
+    ///
+    /// let mut pq = IndexedPriorityQueue::new(|a: &usize, b: &usize| a < b);
+    /// pq.push(3, 10);
+    /// assert_eq!(pq.priority(3), Some(&10));
+    /// assert_eq!(pq.priority(1), None);
+    /// ```
+    pub fn priority(&self, item: usize) -> Option<&P> {
+        self.priorities.get(item).and_then(|priority| priority.as_ref())
+    }
+
+    fn _parent(&self, idx: usize) -> usize {
+        (idx - 1) / 2
+    }
+
+    fn _left_child(&self, idx: usize) -> usize {
+        2 * idx + 1
+    }
+
+    fn _right_child(&self, idx: usize) -> usize {
+        2 * idx + 2
+    }
+
+    fn _priority_at(&self, idx: usize) -> &P {
+        self.priorities[self.heap[idx]].as_ref().unwrap()
+    }
+
+    fn _compare(&self, i: usize, j: usize) -> bool {
+        (self.comparator)(self._priority_at(i), self._priority_at(j))
+    }
+
+    fn _swap(&mut self, i: usize, j: usize) {
+        self.heap.swap(i, j);
+        self.positions[self.heap[i]] = Some(i);
+        self.positions[self.heap[j]] = Some(j);
+    }
+
+    fn _sift_up(&mut self, idx: usize) -> usize {
+        let mut node_index = idx;
+        while node_index > 0 && self._compare(node_index, self._parent(node_index)) {
+            self._swap(node_index, self._parent(node_index));
+            node_index = self._parent(node_index);
+        }
+        node_index
+    }
+
+    fn _sift_down(&mut self, idx: usize) {
+        let mut node_index = idx;
+        while (self._left_child(node_index) < self.size()
+            && self._compare(self._left_child(node_index), node_index))
+            || (self._right_child(node_index) < self.size()
+                && self._compare(self._right_child(node_index), node_index))
+        {
+            let mut greater_index = self._left_child(node_index);
+            if self._right_child(node_index) < self.size()
+                && self._compare(self._right_child(node_index), self._left_child(node_index))
+            {
+                greater_index = self._right_child(node_index);
+            }
+            self._swap(node_index, greater_index);
+            node_index = greater_index;
+        }
+    }
+
+    fn _restore(&mut self, idx: usize) {
+        let node_index = self._sift_up(idx);
+        self._sift_down(node_index);
+    }
+
+    /// Push an item with its priority into the queue and return the size of the queue,
+    /// if the item is already in the queue only its priority is changed
+    ///
+    /// The items index the positions and the priorities, a slot is kept for every item up to the largest one,
+    /// so the items should be dense small ids, e.g. the node ids of a graph.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the slots up to the item overflow the capacity of a `Vec`, e.g. for `usize::MAX / 2`,
+    /// a smaller but still huge item aborts on the allocation failure instead.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flex_algo::IndexedPriorityQueue;
+    ///
+    /// let mut pq = IndexedPriorityQueue::new(|a: &usize, b: &usize| a < b);
+    /// pq.push(0, 14);
+    /// pq.push(1, 10);
+    /// let len = pq.push(1, 12);
+    ///
+    /// assert_eq!(len, 2);
+    /// ```
+    pub fn push(&mut self, item: usize, priority: P) -> usize {
+        if self.contains(item) {
+            self.change_priority(item, priority);
+            return self.size();
+        }
+        if item >= self.positions.len() {
+            self.positions.resize(item + 1, None);
+            self.priorities.resize_with(item + 1, || None);
+        }
+        self.heap.push(item);
+        self.positions[item] = Some(self.heap.len() - 1);
+        self.priorities[item] = Some(priority);
+        self._sift_up(self.heap.len() - 1);
+        self.heap.len()
+    }
+
+    /// Return the first item of the heap with its priority, or `None` if it is empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flex_algo::IndexedPriorityQueue;
+    ///
+    /// let mut pq = IndexedPriorityQueue::new(|a: &usize, b: &usize| a < b);
+    /// pq.push(0, 14);
+    /// pq.push(1, 10);
+    /// pq.push(2, 12);
+    ///
+    /// assert_eq!(pq.pop(), Some((1, 10)));
+    /// ```
+    pub fn pop(&mut self) -> Option<(usize, P)> {
+        let item = *self.heap.first()?;
+        let priority = self.remove(item)?;
+        Some((item, priority))
+    }
+
+    /// Return the first item of the heap with its priority, or `None` if it is empty without change the heap.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flex_algo::IndexedPriorityQueue;
+    ///
+    /// let mut pq = IndexedPriorityQueue::new(|a: &usize, b: &usize| a < b);
+    /// pq.push(0, 14);
+    /// pq.push(1, 10);
+    ///
+    /// assert_eq!(pq.peek(), Some((1, &10)));
+    /// ```
+    pub fn peek(&self) -> Option<(usize, &P)> {
+        let item = *self.heap.first()?;
+        Some((item, self.priorities[item].as_ref().unwrap()))
+    }
+
+    /// Remove the item from the queue and return its priority, or `None` if it is not in the queue
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flex_algo::IndexedPriorityQueue;
+    ///
+    /// let mut pq = IndexedPriorityQueue::new(|a: &usize, b: &usize| a < b);
+    /// pq.push(0, 14);
+    /// pq.push(1, 10);
+    ///
+    /// assert_eq!(pq.remove(1), Some(10));
+    /// assert_eq!(pq.remove(1), None);
+    /// assert_eq!(pq.pop(), Some((0, 14)));
+    /// ```
+    pub fn remove(&mut self, item: usize) -> Option<P> {
+        let idx = (*self.positions.get(item)?)?;
+        let last = self.heap.len() - 1;
+        if idx != last {
+            self._swap(idx, last);
+        }
+        self.heap.pop();
+        self.positions[item] = None;
+        let priority = self.priorities[item].take();
+        if idx < self.heap.len() {
+            self._restore(idx);
+        }
+        priority
+    }
+
+    /// Change the priority of the item and return the old priority, or `None` if it is not in the queue
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flex_algo::IndexedPriorityQueue;
+    ///
+    /// let mut pq = IndexedPriorityQueue::new(|a: &usize, b: &usize| a < b);
+    /// pq.push(0, 14);
+    /// pq.push(1, 10);
+    ///
+    /// assert_eq!(pq.change_priority(1, 20), Some(10));
+    /// assert_eq!(pq.peek(), Some((0, &14)));
+    /// ```
+    pub fn change_priority(&mut self, item: usize, priority: P) -> Option<P> {
+        let idx = (*self.positions.get(item)?)?;
+        let old = self.priorities[item].replace(priority);
+        self._restore(idx);
+        old
+    }
+
+    /// Move the item towards the top of the heap by giving it a higher priority,
+    /// (a smaller key for a min heap), return false if the item is not in the queue
+    /// or the new priority is not higher than the current one
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flex_algo::IndexedPriorityQueue;
+    ///
+    /// let mut pq = IndexedPriorityQueue::new(|a: &usize, b: &usize| a < b);
+    /// pq.push(0, 14);
+    /// pq.push(1, 10);
+    ///
+    /// assert_eq!(pq.decrease_key(0, 20), false);
+    /// assert_eq!(pq.decrease_key(0, 14), false);
+    /// assert_eq!(pq.decrease_key(0, 5), true);
+    /// assert_eq!(pq.peek(), Some((0, &5)));
+    /// ```
+    pub fn decrease_key(&mut self, item: usize, priority: P) -> bool {
+        let idx = match self.positions.get(item) {
+            Some(&Some(idx)) => idx,
+            _ => return false,
+        };
+        // an equal priority is not higher
+        if !(self.comparator)(&priority, self._priority_at(idx)) {
+            return false;
+        }
+        self.priorities[item] = Some(priority);
+        self._sift_up(idx);
+        true
+    }
+
+    /// Move the item towards the bottom of the heap by giving it a lower priority,
+    /// (a greater key for a min heap), return false if the item is not in the queue
+    /// or the new priority is not lower than the current one
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flex_algo::IndexedPriorityQueue;
+    ///
+    /// let mut pq = IndexedPriorityQueue::new(|a: &usize, b: &usize| a < b);
+    /// pq.push(0, 14);
+    /// pq.push(1, 10);
+    ///
+    /// assert_eq!(pq.increase_key(1, 5), false);
+    /// assert_eq!(pq.increase_key(1, 10), false);
+    /// assert_eq!(pq.increase_key(1, 20), true);
+    /// assert_eq!(pq.peek(), Some((0, &14)));
+    /// ```
+    pub fn increase_key(&mut self, item: usize, priority: P) -> bool {
+        let idx = match self.positions.get(item) {
+            Some(&Some(idx)) => idx,
+            _ => return false,
+        };
+        // an equal priority is not lower
+        if !(self.comparator)(self._priority_at(idx), &priority) {
+            return false;
+        }
+        self.priorities[item] = Some(priority);
+        self._sift_down(idx);
+        true
+    }
+}
+
+// serialized as the (item, priority) pairs of the heap, the comparator has to be provided again by
+// `IndexedPriorityQueue::from_vec` after deserializing them as a Vec
+#[cfg(feature = "serde")]
+impl<F, P> serde::Serialize for IndexedPriorityQueue<F, P>
+where
+    F: Fn(&P, &P) -> bool,
+    P: serde::Serialize,
+{
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeSeq;
+
+        let mut seq = serializer.serialize_seq(Some(self.heap.len()))?;
+        for &item in &self.heap {
+            seq.serialize_element(&(item, self.priorities[item].as_ref().unwrap()))?;
+        }
+        seq.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn compare(a: &usize, b: &usize) -> bool {
+        a < b
+    }
+
+    #[test]
+    fn test_indexed_priority_queue_push_pop() {
+        let mut pq = IndexedPriorityQueue::new(compare);
+        assert_eq!(pq.size(), 0);
+        pq.push(0, 14);
+        pq.push(1, 12);
+        pq.push(2, 5);
+        pq.push(3, 7);
+        pq.push(4, 8);
+        pq.push(5, 3);
+        println!("indexed priority queue: {:?}", pq.heap);
+
+        let mut order = Vec::new();
+        while let Some((item, _)) = pq.pop() {
+            order.push(item);
+        }
+        assert_eq!(order, vec![5, 2, 3, 4, 1, 0]);
+        assert!(pq.is_empty());
+    }
+
+    #[test]
+    fn test_indexed_priority_queue_change_priority() {
+        let mut pq = IndexedPriorityQueue::new(compare);
+        for (item, priority) in [(0, 14), (1, 12), (2, 5), (3, 7), (4, 8), (5, 3)] {
+            pq.push(item, priority);
+        }
+        assert!(pq.decrease_key(0, 1));
+        assert!(pq.increase_key(5, 13));
+        assert_eq!(pq.change_priority(1, 6), Some(12));
+        assert_eq!(pq.remove(3), Some(7));
+        assert!(!pq.contains(3));
+        // re-push of an existing item only changes its priority
+        assert_eq!(pq.push(4, 2), 5);
+
+        let mut order = Vec::new();
+        while let Some(pair) = pq.pop() {
+            order.push(pair);
+        }
+        assert_eq!(order, vec![(0, 1), (4, 2), (2, 5), (1, 6), (5, 13)]);
+    }
+
+    #[test]
+    fn test_indexed_priority_queue_equal_priority() {
+        let mut pq = IndexedPriorityQueue::new(compare);
+        pq.push(0, 5);
+        pq.push(1, 5);
+        pq.push(2, 9);
+        // an equal priority is neither higher nor lower, the heap is left as it is
+        assert!(!pq.decrease_key(1, 5));
+        assert!(!pq.increase_key(0, 5));
+        assert_eq!(pq.peek(), Some((0, &5)));
+        assert!(!pq.decrease_key(3, 1));
+        assert!(pq.increase_key(0, 6));
+        assert_eq!(pq.pop(), Some((1, 5)));
+    }
+
+    #[test]
+    #[should_panic(expected = "capacity overflow")]
+    fn test_indexed_priority_queue_huge_item() {
+        let mut pq = IndexedPriorityQueue::new(compare);
+        pq.push(usize::MAX / 2, 1);
+    }
+
+    #[test]
+    fn test_indexed_priority_queue_closure() {
+        let mut pq = IndexedPriorityQueue::new(|a: &f64, b: &f64| a > b);
+        pq.push(7, 1.5);
+        pq.push(2, 6.0);
+        pq.push(9, 0.5);
+        assert_eq!(pq.peek(), Some((2, &6.0)));
+        assert_eq!(pq.priority(9), Some(&0.5));
+        pq.decrease_key(9, 10.0);
+        assert_eq!(pq.pop(), Some((9, 10.0)));
+        assert_eq!(pq.pop(), Some((2, 6.0)));
+        assert_eq!(pq.pop(), Some((7, 1.5)));
+        assert_eq!(pq.pop(), None);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_indexed_priority_queue_serde() {
+        let pq = IndexedPriorityQueue::from_vec(compare, vec![(0, 14), (1, 12), (2, 5), (3, 7)]);
+        let json = serde_json::to_string(&pq).unwrap();
+        let elements: Vec<(usize, usize)> = serde_json::from_str(&json).unwrap();
+        assert_eq!(elements.len(), 4);
+
+        let mut pq = IndexedPriorityQueue::from_vec(compare, elements);
+        assert_eq!(pq.pop(), Some((2, 5)));
+        assert_eq!(pq.pop(), Some((3, 7)));
+    }
+}
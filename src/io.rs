@@ -0,0 +1,1413 @@
+//! Import and export
+//!
+//! This module implements the parsers and the writers of three text formats of a [Graph]:
+//!
+//! - Graphviz DOT: `digraph { "a" -> "b" [weight=3]; }`, the edge weight is read from the `weight` attribute
+//!   or from the `edge [weight=...]` default, the other attributes are ignored
+//!   and a subgraph edge endpoint `a -> {b c}` stands for each of its nodes.
+//! - Edge list: one `source target [weight]` edge or one isolated `node` per line, `#` starts a comment.
+//! - Adjacency JSON: `{"a": ["b", {"target": "c", "weight": 3}], "b": [], "c": []}`,
+//!   every node with the list of its neighbors, with or without a weight.
+//!
+//! The parsers create a node named by its identifier in the order of first appearance
+//! and return a [ParseError] with the line and the column of malformed input.
+//! The writers name every node by its [Label] or else by its id, the nodes are read back by their names
+//! so distinct nodes need distinct names, `to_edge_list` returns a [WriteError] for names it can't write.
+//!
+//! # Example
+//!
+//! ```
+//! use flex_algo::{Dijkstra, Graph};
+//!
+//! let graph: Graph<String, Option<u32>> = Graph::from_edge_list("a b 4\nb c 1\na c 7\n").unwrap();
+//! assert_eq!(graph.node(2), Some(&"c".to_string()));
+//!
+//! // the edges without a weight cost 1
+//! let dijkstra = Dijkstra::from(graph.map(|_, _| (), |_, weight| weight.unwrap_or(1)));
+//! assert_eq!(dijkstra.shortest_paths(0).path_to(2), Some((5, vec![0, 1, 2])));
+//! println!("{}", graph.to_dot());
+//!
+//! let error = Graph::<String, Option<u32>>::from_edge_list("a b 4\nb c x\n").unwrap_err();
+//! assert_eq!((error.line(), error.column()), (2, 5));
+//! assert_eq!(error.to_string(), "line 2, column 5: invalid weight: x");
+//! ```
+//!
+use std::collections::{HashMap, HashSet};
+use std::fmt::{Display, Formatter, Write};
+use std::str::FromStr;
+use crate::graph::Graph;
+
+/// ParseError
+///
+/// The line and the column, both counted from 1, of the malformed input and what was wrong there.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    line: usize,
+    column: usize,
+    message: String,
+}
+
+impl ParseError {
+    fn new(line: usize, column: usize, message: impl Into<String>) -> Self {
+        ParseError {
+            line,
+            column,
+            message: message.into(),
+        }
+    }
+
+    /// Return the line of the error
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// Return the column of the error, in characters
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    /// Return the description of the error
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}, column {}: {}", self.line, self.column, self.message)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// WriteError
+///
+/// A node name or an edge weight, formatted with `Debug`, which can't be written in the edge list format
+/// and parsed back to the same graph.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WriteError {
+    /// The node name is empty or contains whitespace or `#`
+    InvalidName(String),
+    /// The node name is the name of another node
+    DuplicateName(String),
+    /// The edge weight is empty or contains whitespace or `#`
+    InvalidWeight(String),
+}
+
+impl Display for WriteError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WriteError::InvalidName(name) => write!(f, "invalid node name: {}", name),
+            WriteError::DuplicateName(name) => write!(f, "duplicate node name: {}", name),
+            WriteError::InvalidWeight(weight) => write!(f, "invalid edge weight: {}", weight),
+        }
+    }
+}
+
+impl std::error::Error for WriteError {}
+
+/// Label
+///
+/// The text of a node or an edge payload in the exported formats, `None` for no text:
+/// a node without a label is named by its id and an edge without a label has no weight.
+///
+/// # Example
+///
+/// ```
+/// use flex_algo::Label;
+///
+/// assert_eq!(().label(), None);
+/// assert_eq!("a".label(), Some("a".to_string()));
+/// assert_eq!(Some(2.5).label(), Some("2.5".to_string()));
+/// ```
+pub trait Label {
+    /// Return the text of the payload
+    fn label(&self) -> Option<String>;
+}
+
+impl Label for () {
+    fn label(&self) -> Option<String> {
+        None
+    }
+}
+
+impl Label for str {
+    fn label(&self) -> Option<String> {
+        Some(self.to_string())
+    }
+}
+
+impl Label for String {
+    fn label(&self) -> Option<String> {
+        Some(self.clone())
+    }
+}
+
+impl<T: Label + ?Sized> Label for &T {
+    fn label(&self) -> Option<String> {
+        (**self).label()
+    }
+}
+
+impl<T: Label> Label for Option<T> {
+    fn label(&self) -> Option<String> {
+        self.as_ref().and_then(Label::label)
+    }
+}
+
+macro_rules! impl_display_label {
+    ($($t:ty),*) => {
+        $(
+            impl Label for $t {
+                fn label(&self) -> Option<String> {
+                    Some(self.to_string())
+                }
+            }
+        )*
+    };
+}
+
+impl_display_label!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64, char, bool);
+
+impl<W: FromStr> Graph<String, Option<W>> {
+    /// Parse a Graphviz DOT `digraph`, or a `graph` whose edges keep the order of their nodes,
+    /// the edge weight is read from the last `weight` attribute of the edge or of the `edge` defaults in scope,
+    /// a subgraph endpoint connects each of its nodes
+    /// and the subgraphs nest at most 128 levels deep
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flex_algo::Graph;
+    ///
+    /// let input = r#"
+    ///     digraph dependencies {
+    ///         node [shape=box];
+    ///         app -> core -> "std lib" [weight=2];
+    ///         app -> log [label=1];
+    ///         test -> {core log};
+    ///     }
+    /// "#;
+    /// let graph: Graph<String, Option<u32>> = Graph::from_dot(input).unwrap();
+    /// assert_eq!(graph.node(2), Some(&"std lib".to_string()));
+    /// assert_eq!(graph.edge(1).unwrap().weight(), &Some(2));
+    /// assert_eq!(graph.edge(2).unwrap().weight(), &None);
+    /// assert_eq!(graph.edge_count(), 5);
+    /// ```
+    pub fn from_dot(input: &str) -> Result<Self, ParseError> {
+        let tokens = _dot_tokens(input)?;
+        let mut parser = DotParser {
+            tokens,
+            index: 0,
+            end: _end_position(input),
+            builder: Builder::new(),
+            subgraphs: Vec::new(),
+            edge_weights: vec![None],
+            directed: true,
+            strict: false,
+        };
+        parser.parse()?;
+        Ok(parser.builder.graph)
+    }
+
+    /// Parse an edge list with one `source target [weight]` edge or one isolated `node` per line,
+    /// the fields are separated by whitespace and `#` starts a comment
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flex_algo::Graph;
+    ///
+    /// let input = "# roads\na b 1.5\nb c\nd\n";
+    /// let graph: Graph<String, Option<f64>> = Graph::from_edge_list(input).unwrap();
+    /// assert_eq!(graph.node_count(), 4);
+    /// assert_eq!(graph.edge(0).unwrap().weight(), &Some(1.5));
+    /// assert_eq!(graph.edge(1).unwrap().weight(), &None);
+    /// ```
+    pub fn from_edge_list(input: &str) -> Result<Self, ParseError> {
+        let mut builder = Builder::new();
+        for (i, line) in input.lines().enumerate() {
+            let line = line.split('#').next().unwrap();
+            // the fields with their column
+            let mut fields = Vec::new();
+            let mut start = None;
+            for (column, c) in line.chars().chain(std::iter::once(' ')).enumerate() {
+                match (c.is_whitespace(), start) {
+                    (false, None) => start = Some(column),
+                    (true, Some(first)) => {
+                        let field: String = line.chars().skip(first).take(column - first).collect();
+                        fields.push((field, first + 1));
+                        start = None;
+                    }
+                    _ => {}
+                }
+            }
+            match fields.as_slice() {
+                [] => {}
+                [(node, _)] => {
+                    builder.node(node);
+                }
+                [(source, _), (target, _)] => builder.edge(source, target, None),
+                [(source, _), (target, _), (weight, column)] => {
+                    let weight = _parse_weight(weight, i + 1, *column)?;
+                    builder.edge(source, target, Some(weight));
+                }
+                [_, _, _, (field, column), ..] => {
+                    return Err(ParseError::new(i + 1, *column, format!("unexpected field: {}", field)));
+                }
+            }
+        }
+        Ok(builder.graph)
+    }
+
+    /// Parse an adjacency list JSON object with the list of the neighbors of every node,
+    /// a neighbor is a node name or an object `{"target": name, "weight": weight}`
+    /// with a number, a string or a null weight
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flex_algo::Graph;
+    ///
+    /// let input = r#"{"a": ["b", {"target": "c", "weight": 3}], "b": ["c"], "c": []}"#;
+    /// let graph: Graph<String, Option<i32>> = Graph::from_adjacency_json(input).unwrap();
+    /// assert_eq!(graph.edge_count(), 3);
+    /// assert_eq!(graph.edge(1).unwrap().weight(), &Some(3));
+    ///
+    /// let error = Graph::<String, Option<i32>>::from_adjacency_json("{\"a\": [\"b\",]}").unwrap_err();
+    /// assert_eq!(error.to_string(), "line 1, column 12: expected a node name or an edge object");
+    /// ```
+    pub fn from_adjacency_json(input: &str) -> Result<Self, ParseError> {
+        let mut cursor = Cursor::new(input);
+        let adjacency = _json_adjacency(&mut cursor)?;
+
+        let mut builder = Builder::new();
+        // the nodes take the order of the keys, the targets without a key come after them
+        for (name, _) in &adjacency {
+            builder.node(name);
+        }
+        for (name, neighbors) in adjacency {
+            for (target, weight) in neighbors {
+                let weight = match weight {
+                    Some((weight, line, column)) => Some(_parse_weight(&weight, line, column)?),
+                    None => None,
+                };
+                builder.edge(&name, &target, weight);
+            }
+        }
+        Ok(builder.graph)
+    }
+}
+
+impl<N: Label, E: Label> Graph<N, E> {
+    /// Write the graph in the Graphviz DOT format, every node is declared before the edges,
+    /// the edge weights are written as labels and the numeric ones also as weights
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flex_algo::Graph;
+    ///
+    /// let graph = Graph::from_edges(vec!["a", "b"], vec![(0, 1, 2)]);
+    /// assert_eq!(graph.to_dot(), "digraph {\n    \"a\";\n    \"b\";\n    \"a\" -> \"b\" [label=\"2\", weight=\"2\"];\n}\n");
+    /// ```
+    pub fn to_dot(&self) -> String {
+        let names = self._names();
+        let mut output = String::from("digraph {\n");
+        for (id, _) in self.nodes() {
+            writeln!(output, "    {};", _dot_quote(&names[id])).unwrap();
+        }
+        for (_, edge) in self.edges() {
+            write!(output, "    {} -> {}", _dot_quote(&names[edge.source()]), _dot_quote(&names[edge.target()])).unwrap();
+            match edge.weight().label() {
+                Some(label) if _is_json_number(&label) => {
+                    write!(output, " [label={}, weight={}]", _dot_quote(&label), _dot_quote(&label)).unwrap();
+                }
+                Some(label) => write!(output, " [label={}]", _dot_quote(&label)).unwrap(),
+                None => {}
+            }
+            output.push_str(";\n");
+        }
+        output.push_str("}\n");
+        output
+    }
+
+    /// Write the graph as an edge list, the edges in the order of their ids and then the isolated nodes,
+    /// or an error if a name or a weight is empty or contains whitespace or `#`, or if two nodes have the same name
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flex_algo::{Graph, WriteError};
+    ///
+    /// let graph = Graph::new(3, vec![(1, 0)]);
+    /// assert_eq!(graph.to_edge_list(), Ok("0 1\n2\n".to_string()));
+    ///
+    /// let graph = Graph::from_edges(vec!["app", "std lib"], vec![(0, 1, ())]);
+    /// assert_eq!(graph.to_edge_list(), Err(WriteError::InvalidName("\"std lib\"".to_string())));
+    /// ```
+    pub fn to_edge_list(&self) -> Result<String, WriteError> {
+        let names = self._names();
+        let mut seen = HashSet::new();
+        for (id, _) in self.nodes() {
+            if !_is_edge_list_field(&names[id]) {
+                return Err(WriteError::InvalidName(format!("{:?}", names[id])));
+            }
+            if !seen.insert(&names[id]) {
+                return Err(WriteError::DuplicateName(format!("{:?}", names[id])));
+            }
+        }
+        let mut output = String::new();
+        for (_, edge) in self.edges() {
+            write!(output, "{} {}", names[edge.source()], names[edge.target()]).unwrap();
+            if let Some(label) = edge.weight().label() {
+                if !_is_edge_list_field(&label) {
+                    return Err(WriteError::InvalidWeight(format!("{:?}", label)));
+                }
+                write!(output, " {}", label).unwrap();
+            }
+            output.push('\n');
+        }
+        for (id, _) in self.nodes() {
+            if self.degree(id) == 0 {
+                writeln!(output, "{}", names[id]).unwrap();
+            }
+        }
+        Ok(output)
+    }
+
+    /// Write the graph as an adjacency list JSON object, one node per line in the order of the ids,
+    /// a weight is written as a number when it is a JSON number and as a string otherwise
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flex_algo::Graph;
+    ///
+    /// let graph = Graph::from_edges(vec!["a", "b"], vec![(0, 1, Some(2)), (0, 1, None)]);
+    /// assert_eq!(graph.to_adjacency_json(), "{\n  \"a\": [{\"target\": \"b\", \"weight\": 2}, \"b\"],\n  \"b\": []\n}\n");
+    /// ```
+    pub fn to_adjacency_json(&self) -> String {
+        let names = self._names();
+        let mut output = String::from("{");
+        for (i, (id, _)) in self.nodes().enumerate() {
+            if i > 0 {
+                output.push(',');
+            }
+            write!(output, "\n  {}: [", _json_quote(&names[id])).unwrap();
+            for (j, edge) in self.out_edges(id).enumerate() {
+                if j > 0 {
+                    output.push_str(", ");
+                }
+                let target = _json_quote(&names[edge.target()]);
+                match edge.weight().label() {
+                    Some(label) if _is_json_number(&label) => {
+                        write!(output, "{{\"target\": {}, \"weight\": {}}}", target, label).unwrap();
+                    }
+                    Some(label) => {
+                        write!(output, "{{\"target\": {}, \"weight\": {}}}", target, _json_quote(&label)).unwrap();
+                    }
+                    None => output.push_str(&target),
+                }
+            }
+            output.push(']');
+        }
+        if self.node_count() > 0 {
+            output.push('\n');
+        }
+        output.push_str("}\n");
+        output
+    }
+
+    // the name of every node by its id, the removed ones are left empty
+    fn _names(&self) -> Vec<String> {
+        let mut names = vec![String::new(); self.node_bound()];
+        for (id, node) in self.nodes() {
+            names[id] = node.label().unwrap_or_else(|| id.to_string());
+        }
+        names
+    }
+}
+
+// the graph being parsed with the id of every node name
+struct Builder<W> {
+    graph: Graph<String, Option<W>>,
+    ids: HashMap<String, usize>,
+}
+
+impl<W> Builder<W> {
+    fn new() -> Self {
+        Builder {
+            graph: Graph::default(),
+            ids: HashMap::new(),
+        }
+    }
+
+    fn node(&mut self, name: &str) -> usize {
+        if let Some(&id) = self.ids.get(name) {
+            return id;
+        }
+        let id = self.graph.add_node(name.to_string());
+        self.ids.insert(name.to_string(), id);
+        id
+    }
+
+    fn edge(&mut self, source: &str, target: &str, weight: Option<W>) {
+        let source = self.node(source);
+        let target = self.node(target);
+        self.graph.add_edge(source, target, weight);
+    }
+}
+
+fn _parse_weight<W: FromStr>(text: &str, line: usize, column: usize) -> Result<W, ParseError> {
+    text.parse().map_err(|_| ParseError::new(line, column, format!("invalid weight: {}", text)))
+}
+
+// the line and the column just after the input
+fn _end_position(input: &str) -> (usize, usize) {
+    let mut cursor = Cursor::new(input);
+    while cursor.bump().is_some() {}
+    cursor.position()
+}
+
+// a position in the input counting the lines and the columns
+struct Cursor {
+    chars: Vec<char>,
+    index: usize,
+    line: usize,
+    column: usize,
+}
+
+impl Cursor {
+    fn new(input: &str) -> Self {
+        Cursor {
+            chars: input.chars().collect(),
+            index: 0,
+            line: 1,
+            column: 1,
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.index).copied()
+    }
+
+    fn peek_next(&self) -> Option<char> {
+        self.chars.get(self.index + 1).copied()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.index += 1;
+        if c == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+        Some(c)
+    }
+
+    fn position(&self) -> (usize, usize) {
+        (self.line, self.column)
+    }
+
+    fn error(&self, message: impl Into<String>) -> ParseError {
+        ParseError::new(self.line, self.column, message)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum DotToken {
+    // an identifier, a numeral or a quoted string, the keywords are the unquoted identifiers
+    Id { text: String, quoted: bool },
+    LeftBrace,
+    RightBrace,
+    LeftBracket,
+    RightBracket,
+    Semicolon,
+    Comma,
+    Equal,
+    Colon,
+    // ->
+    Arrow,
+    // --
+    Line,
+}
+
+fn _dot_tokens(input: &str) -> Result<Vec<(DotToken, usize, usize)>, ParseError> {
+    let mut cursor = Cursor::new(input);
+    let mut tokens = Vec::new();
+    while let Some(c) = cursor.peek() {
+        let (line, column) = cursor.position();
+        let token = match c {
+            c if c.is_whitespace() => {
+                cursor.bump();
+                continue;
+            }
+            '/' if cursor.peek_next() == Some('/') => {
+                while cursor.peek().is_some_and(|c| c != '\n') {
+                    cursor.bump();
+                }
+                continue;
+            }
+            // a preprocessor output line
+            '#' if column == 1 => {
+                while cursor.peek().is_some_and(|c| c != '\n') {
+                    cursor.bump();
+                }
+                continue;
+            }
+            '/' if cursor.peek_next() == Some('*') => {
+                cursor.bump();
+                cursor.bump();
+                while !(cursor.peek() == Some('*') && cursor.peek_next() == Some('/')) {
+                    if cursor.bump().is_none() {
+                        return Err(ParseError::new(line, column, "unterminated comment"));
+                    }
+                }
+                cursor.bump();
+                cursor.bump();
+                continue;
+            }
+            '{' => DotToken::LeftBrace,
+            '}' => DotToken::RightBrace,
+            '[' => DotToken::LeftBracket,
+            ']' => DotToken::RightBracket,
+            ';' => DotToken::Semicolon,
+            ',' => DotToken::Comma,
+            '=' => DotToken::Equal,
+            ':' => DotToken::Colon,
+            '-' if cursor.peek_next() == Some('>') => {
+                cursor.bump();
+                DotToken::Arrow
+            }
+            '-' if cursor.peek_next() == Some('-') => {
+                cursor.bump();
+                DotToken::Line
+            }
+            '"' => {
+                cursor.bump();
+                let mut text = String::new();
+                loop {
+                    match cursor.bump() {
+                        None => return Err(ParseError::new(line, column, "unterminated string")),
+                        Some('"') => break,
+                        Some('\\') if cursor.peek() == Some('"') || cursor.peek() == Some('\\') => {
+                            text.push(cursor.bump().unwrap());
+                        }
+                        // a line continuation
+                        Some('\\') if cursor.peek() == Some('\n') => {
+                            cursor.bump();
+                        }
+                        Some(c) => text.push(c),
+                    }
+                }
+                tokens.push((DotToken::Id { text, quoted: true }, line, column));
+                continue;
+            }
+            c if c == '-' || c == '.' || c.is_ascii_digit() => {
+                let mut text = String::new();
+                text.push(cursor.bump().unwrap());
+                while cursor.peek().is_some_and(|c| c.is_ascii_digit() || (c == '.' && !text.contains('.'))) {
+                    text.push(cursor.bump().unwrap());
+                }
+                if !text.chars().any(|c| c.is_ascii_digit()) {
+                    return Err(ParseError::new(line, column, format!("invalid numeral: {}", text)));
+                }
+                tokens.push((DotToken::Id { text, quoted: false }, line, column));
+                continue;
+            }
+            c if c == '_' || c.is_alphabetic() || !c.is_ascii() => {
+                let mut text = String::new();
+                while cursor.peek().is_some_and(|c| c == '_' || c.is_alphanumeric() || !c.is_ascii()) {
+                    text.push(cursor.bump().unwrap());
+                }
+                tokens.push((DotToken::Id { text, quoted: false }, line, column));
+                continue;
+            }
+            c => return Err(ParseError::new(line, column, format!("unexpected character: {:?}", c))),
+        };
+        cursor.bump();
+        tokens.push((token, line, column));
+    }
+    Ok(tokens)
+}
+
+// the maximum nesting of the subgraphs, which bounds the recursion of the parser
+const DOT_MAX_DEPTH: usize = 128;
+
+// a recursive descent parser of the statements of a DOT graph,
+// the subgraphs are flattened into the graph and a subgraph endpoint connects all of its nodes
+struct DotParser<W> {
+    tokens: Vec<(DotToken, usize, usize)>,
+    index: usize,
+    // the position just after the input
+    end: (usize, usize),
+    builder: Builder<W>,
+    // the nodes of the enclosing subgraphs, innermost last
+    subgraphs: Vec<Vec<usize>>,
+    // the default edge weight tuple(value, line, column) of the graph and of every enclosing subgraph
+    edge_weights: Vec<Option<(String, usize, usize)>>,
+    directed: bool,
+    strict: bool,
+}
+
+impl<W: FromStr> DotParser<W> {
+    fn parse(&mut self) -> Result<(), ParseError> {
+        self.strict = self._keyword("strict");
+        if self._keyword("digraph") {
+            self.directed = true;
+        } else if self._keyword("graph") {
+            self.directed = false;
+        } else {
+            return Err(self._error("expected 'graph' or 'digraph'"));
+        }
+        if let Some((DotToken::Id { .. }, _, _)) = self._peek() {
+            self.index += 1;
+        }
+        self._expect(DotToken::LeftBrace, "expected '{'")?;
+        self._statements()?;
+        if self.index < self.tokens.len() {
+            return Err(self._error("unexpected input after the graph"));
+        }
+        Ok(())
+    }
+
+    // the statements up to and including the closing brace
+    fn _statements(&mut self) -> Result<(), ParseError> {
+        loop {
+            match self._peek() {
+                Some((DotToken::RightBrace, _, _)) => {
+                    self.index += 1;
+                    return Ok(());
+                }
+                None => return Err(self._error("expected '}'")),
+                _ => self._statement()?,
+            }
+            if let Some((DotToken::Semicolon, _, _)) = self._peek() {
+                self.index += 1;
+            }
+        }
+    }
+
+    fn _statement(&mut self) -> Result<(), ParseError> {
+        if self._keyword("edge") {
+            let attributes = self._attributes()?;
+            if let Some(weight) = _dot_weight(attributes) {
+                *self.edge_weights.last_mut().unwrap() = Some(weight);
+            }
+            return Ok(());
+        }
+        if self._keyword("graph") || self._keyword("node") {
+            self._attributes()?;
+            return Ok(());
+        }
+
+        // the endpoints of an edge statement, a subgraph endpoint is the set of its nodes
+        let mut endpoints = if self._is_subgraph() {
+            vec![self._subgraph()?]
+        } else {
+            let (name, _, _) = self._node_id()?;
+            // a graph attribute
+            if let Some((DotToken::Equal, _, _)) = self._peek() {
+                self.index += 1;
+                self._id()?;
+                return Ok(());
+            }
+            vec![vec![self._node(&name)]]
+        };
+        while let Some((token, line, column)) = self._peek() {
+            let (line, column) = (*line, *column);
+            match token {
+                DotToken::Arrow if !self.directed => return Err(ParseError::new(line, column, "expected '--' in a graph")),
+                DotToken::Line if self.directed => return Err(ParseError::new(line, column, "expected '->' in a digraph")),
+                DotToken::Arrow | DotToken::Line => {
+                    self.index += 1;
+                    let endpoint = if self._is_subgraph() {
+                        self._subgraph()?
+                    } else {
+                        let (name, _, _) = self._node_id()?;
+                        vec![self._node(&name)]
+                    };
+                    endpoints.push(endpoint);
+                }
+                _ => break,
+            }
+        }
+        let attributes = self._attributes()?;
+
+        // only the weight attribute is a weight, the label is ignored
+        let weight = _dot_weight(attributes).or_else(|| self.edge_weights.last().unwrap().clone());
+        for pair in endpoints.windows(2) {
+            for &source in &pair[0] {
+                for &target in &pair[1] {
+                    let weight = match &weight {
+                        Some((value, line, column)) => Some(_parse_weight(value, *line, *column)?),
+                        None => None,
+                    };
+                    let duplicate = self.builder.graph.contains_edge(source, target)
+                        || (!self.directed && self.builder.graph.contains_edge(target, source));
+                    if !(self.strict && duplicate) {
+                        self.builder.graph.add_edge(source, target, weight);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // whether a subgraph `subgraph [id] { ... }` or `{ ... }` follows
+    fn _is_subgraph(&self) -> bool {
+        match self._peek() {
+            Some((DotToken::Id { text, quoted: false }, _, _)) => text.eq_ignore_ascii_case("subgraph"),
+            Some((DotToken::LeftBrace, _, _)) => true,
+            _ => false,
+        }
+    }
+
+    // the subgraph statements with the ids of the nodes in the subgraph
+    fn _subgraph(&mut self) -> Result<Vec<usize>, ParseError> {
+        if self._keyword("subgraph") {
+            if let Some((DotToken::Id { .. }, _, _)) = self._peek() {
+                self.index += 1;
+            }
+        }
+        if self.subgraphs.len() == DOT_MAX_DEPTH {
+            return Err(self._error("too deeply nested subgraphs"));
+        }
+        self._expect(DotToken::LeftBrace, "expected '{'")?;
+        self.subgraphs.push(Vec::new());
+        self.edge_weights.push(self.edge_weights.last().unwrap().clone());
+        let result = self._statements();
+        self.edge_weights.pop();
+        let nodes = self.subgraphs.pop().unwrap();
+        result.map(|_| nodes)
+    }
+
+    // the id of the node, added to every enclosing subgraph
+    fn _node(&mut self, name: &str) -> usize {
+        let id = self.builder.node(name);
+        for nodes in &mut self.subgraphs {
+            if !nodes.contains(&id) {
+                nodes.push(id);
+            }
+        }
+        id
+    }
+
+    // the attribute lists tuple(key, value, line, column) of the value
+    fn _attributes(&mut self) -> Result<Vec<(String, String, usize, usize)>, ParseError> {
+        let mut attributes = Vec::new();
+        while let Some((DotToken::LeftBracket, _, _)) = self._peek() {
+            self.index += 1;
+            loop {
+                if let Some((DotToken::RightBracket, _, _)) = self._peek() {
+                    self.index += 1;
+                    break;
+                }
+                let (key, _, _) = self._id()?;
+                self._expect(DotToken::Equal, "expected '='")?;
+                let (value, line, column) = self._id()?;
+                attributes.push((key, value, line, column));
+                if let Some((DotToken::Semicolon | DotToken::Comma, _, _)) = self._peek() {
+                    self.index += 1;
+                }
+            }
+        }
+        Ok(attributes)
+    }
+
+    // a node identifier with an optional port
+    fn _node_id(&mut self) -> Result<(String, usize, usize), ParseError> {
+        let id = self._id().map_err(|error| ParseError::new(error.line, error.column, "expected a node"))?;
+        while let Some((DotToken::Colon, _, _)) = self._peek() {
+            self.index += 1;
+            self._id()?;
+        }
+        Ok(id)
+    }
+
+    fn _id(&mut self) -> Result<(String, usize, usize), ParseError> {
+        match self._peek() {
+            Some((DotToken::Id { text, .. }, line, column)) => {
+                let id = (text.clone(), *line, *column);
+                self.index += 1;
+                Ok(id)
+            }
+            _ => Err(self._error("expected an identifier")),
+        }
+    }
+
+    // consume the unquoted keyword, in any case
+    fn _keyword(&mut self, keyword: &str) -> bool {
+        match self._peek() {
+            Some((DotToken::Id { text, quoted: false }, _, _)) if text.eq_ignore_ascii_case(keyword) => {
+                self.index += 1;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn _expect(&mut self, token: DotToken, message: &str) -> Result<(), ParseError> {
+        match self._peek() {
+            Some((next, _, _)) if *next == token => {
+                self.index += 1;
+                Ok(())
+            }
+            _ => Err(self._error(message)),
+        }
+    }
+
+    fn _peek(&self) -> Option<&(DotToken, usize, usize)> {
+        self.tokens.get(self.index)
+    }
+
+    // an error at the next token or at the end of the input
+    fn _error(&self, message: &str) -> ParseError {
+        let (line, column) = match self._peek() {
+            Some((_, line, column)) => (*line, *column),
+            None => self.end,
+        };
+        ParseError::new(line, column, message)
+    }
+}
+
+// the last weight attribute tuple(value, line, column) of an attribute list
+fn _dot_weight(attributes: Vec<(String, String, usize, usize)>) -> Option<(String, usize, usize)> {
+    attributes
+        .into_iter()
+        .rfind(|(key, _, _, _)| key == "weight")
+        .map(|(_, value, line, column)| (value, line, column))
+}
+
+fn _dot_quote(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+// a neighbor tuple(target, weight text with its line and column)
+type JsonNeighbor = (String, Option<(String, usize, usize)>);
+
+// the neighbors of every key
+type JsonAdjacency = Vec<(String, Vec<JsonNeighbor>)>;
+
+fn _json_adjacency(cursor: &mut Cursor) -> Result<JsonAdjacency, ParseError> {
+    let mut adjacency = Vec::new();
+    let mut keys = HashSet::new();
+    _json_whitespace(cursor);
+    _json_expect(cursor, '{', "expected '{'")?;
+    _json_whitespace(cursor);
+    if cursor.peek() == Some('}') {
+        cursor.bump();
+    } else {
+        loop {
+            _json_whitespace(cursor);
+            let (line, column) = cursor.position();
+            if cursor.peek() != Some('"') {
+                return Err(cursor.error("expected a node name"));
+            }
+            let name = _json_string(cursor)?;
+            if !keys.insert(name.clone()) {
+                return Err(ParseError::new(line, column, format!("duplicate node: {}", name)));
+            }
+            _json_whitespace(cursor);
+            _json_expect(cursor, ':', "expected ':'")?;
+            _json_whitespace(cursor);
+            _json_expect(cursor, '[', "expected a list of neighbors")?;
+            let mut neighbors = Vec::new();
+            _json_whitespace(cursor);
+            if cursor.peek() == Some(']') {
+                cursor.bump();
+            } else {
+                loop {
+                    _json_whitespace(cursor);
+                    neighbors.push(_json_neighbor(cursor)?);
+                    _json_whitespace(cursor);
+                    match cursor.peek() {
+                        Some(',') => {}
+                        Some(']') => break,
+                        _ => return Err(cursor.error("expected ',' or ']'")),
+                    }
+                    cursor.bump();
+                }
+                cursor.bump();
+            }
+            adjacency.push((name, neighbors));
+            _json_whitespace(cursor);
+            match cursor.peek() {
+                Some(',') => {}
+                Some('}') => break,
+                _ => return Err(cursor.error("expected ',' or '}'")),
+            }
+            cursor.bump();
+        }
+        cursor.bump();
+    }
+    _json_whitespace(cursor);
+    if cursor.peek().is_some() {
+        return Err(cursor.error("unexpected input after the graph"));
+    }
+    Ok(adjacency)
+}
+
+// a neighbor name or an edge object {"target": name, "weight": weight}
+fn _json_neighbor(cursor: &mut Cursor) -> Result<JsonNeighbor, ParseError> {
+    match cursor.peek() {
+        Some('"') => Ok((_json_string(cursor)?, None)),
+        Some('{') => {
+            let (line, column) = cursor.position();
+            cursor.bump();
+            let mut target = None;
+            let mut weight = None;
+            _json_whitespace(cursor);
+            if cursor.peek() == Some('}') {
+                cursor.bump();
+            } else {
+                loop {
+                    _json_whitespace(cursor);
+                    let (key_line, key_column) = cursor.position();
+                    if cursor.peek() != Some('"') {
+                        return Err(cursor.error("expected a key"));
+                    }
+                    let key = _json_string(cursor)?;
+                    _json_whitespace(cursor);
+                    _json_expect(cursor, ':', "expected ':'")?;
+                    _json_whitespace(cursor);
+                    match key.as_str() {
+                        "target" if cursor.peek() == Some('"') => target = Some(_json_string(cursor)?),
+                        "target" => return Err(cursor.error("expected a node name")),
+                        "weight" => weight = _json_weight(cursor)?,
+                        _ => return Err(ParseError::new(key_line, key_column, format!("unexpected key: {}", key))),
+                    }
+                    _json_whitespace(cursor);
+                    match cursor.peek() {
+                        Some(',') => {}
+                        Some('}') => break,
+                        _ => return Err(cursor.error("expected ',' or '}'")),
+                    }
+                    cursor.bump();
+                }
+                cursor.bump();
+            }
+            match target {
+                Some(target) => Ok((target, weight)),
+                None => Err(ParseError::new(line, column, "missing target")),
+            }
+        }
+        _ => Err(cursor.error("expected a node name or an edge object")),
+    }
+}
+
+// the text of a number or a string weight, None for null
+fn _json_weight(cursor: &mut Cursor) -> Result<Option<(String, usize, usize)>, ParseError> {
+    let (line, column) = cursor.position();
+    match cursor.peek() {
+        Some('"') => Ok(Some((_json_string(cursor)?, line, column))),
+        Some('n') => {
+            for c in "null".chars() {
+                if cursor.peek() != Some(c) {
+                    return Err(ParseError::new(line, column, "expected a weight"));
+                }
+                cursor.bump();
+            }
+            Ok(None)
+        }
+        Some(c) if c == '-' || c.is_ascii_digit() => Ok(Some((_json_number(cursor)?, line, column))),
+        _ => Err(cursor.error("expected a weight")),
+    }
+}
+
+fn _json_number(cursor: &mut Cursor) -> Result<String, ParseError> {
+    let (line, column) = cursor.position();
+    let invalid = || ParseError::new(line, column, "invalid number");
+    let mut text = String::new();
+    let digits = |cursor: &mut Cursor, text: &mut String| {
+        let length = text.len();
+        while let Some(c) = cursor.peek().filter(char::is_ascii_digit) {
+            text.push(c);
+            cursor.bump();
+        }
+        text.len() > length
+    };
+    if cursor.peek() == Some('-') {
+        text.push('-');
+        cursor.bump();
+    }
+    // no leading zero
+    if cursor.peek() == Some('0') {
+        text.push('0');
+        cursor.bump();
+    } else if !digits(cursor, &mut text) {
+        return Err(invalid());
+    }
+    if cursor.peek() == Some('.') {
+        text.push('.');
+        cursor.bump();
+        if !digits(cursor, &mut text) {
+            return Err(invalid());
+        }
+    }
+    if let Some(c) = cursor.peek().filter(|&c| c == 'e' || c == 'E') {
+        text.push(c);
+        cursor.bump();
+        if let Some(sign) = cursor.peek().filter(|&c| c == '+' || c == '-') {
+            text.push(sign);
+            cursor.bump();
+        }
+        if !digits(cursor, &mut text) {
+            return Err(invalid());
+        }
+    }
+    Ok(text)
+}
+
+fn _json_string(cursor: &mut Cursor) -> Result<String, ParseError> {
+    let (line, column) = cursor.position();
+    cursor.bump();
+    let mut text = String::new();
+    loop {
+        let (escape_line, escape_column) = cursor.position();
+        match cursor.bump() {
+            None => return Err(ParseError::new(line, column, "unterminated string")),
+            Some('"') => return Ok(text),
+            Some('\\') => {
+                let c = match cursor.bump() {
+                    Some('"') => '"',
+                    Some('\\') => '\\',
+                    Some('/') => '/',
+                    Some('b') => '\u{8}',
+                    Some('f') => '\u{c}',
+                    Some('n') => '\n',
+                    Some('r') => '\r',
+                    Some('t') => '\t',
+                    Some('u') => {
+                        let invalid = || ParseError::new(escape_line, escape_column, "invalid unicode escape");
+                        let high = _json_hex(cursor).ok_or_else(invalid)?;
+                        let code = if (0xD800..0xDC00).contains(&high) {
+                            // a surrogate pair
+                            if cursor.bump() != Some('\\') || cursor.bump() != Some('u') {
+                                return Err(invalid());
+                            }
+                            let low = _json_hex(cursor).filter(|low| (0xDC00..0xE000).contains(low)).ok_or_else(invalid)?;
+                            0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00)
+                        } else {
+                            high
+                        };
+                        char::from_u32(code).ok_or_else(invalid)?
+                    }
+                    _ => return Err(ParseError::new(escape_line, escape_column, "invalid escape")),
+                };
+                text.push(c);
+            }
+            Some(c) if (c as u32) < 0x20 => {
+                return Err(ParseError::new(escape_line, escape_column, "invalid character in string"));
+            }
+            Some(c) => text.push(c),
+        }
+    }
+}
+
+fn _json_hex(cursor: &mut Cursor) -> Option<u32> {
+    let mut code = 0;
+    for _ in 0..4 {
+        code = code * 16 + cursor.bump()?.to_digit(16)?;
+    }
+    Some(code)
+}
+
+fn _json_whitespace(cursor: &mut Cursor) {
+    while cursor.peek().is_some_and(|c| matches!(c, ' ' | '\t' | '\n' | '\r')) {
+        cursor.bump();
+    }
+}
+
+fn _json_expect(cursor: &mut Cursor, expected: char, message: &str) -> Result<(), ParseError> {
+    if cursor.peek() != Some(expected) {
+        return Err(cursor.error(message));
+    }
+    cursor.bump();
+    Ok(())
+}
+
+fn _json_quote(text: &str) -> String {
+    let mut quoted = String::from("\"");
+    for c in text.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if (c as u32) < 0x20 => write!(quoted, "\\u{:04x}", c as u32).unwrap(),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+// a field read back as is by `from_edge_list`
+fn _is_edge_list_field(text: &str) -> bool {
+    !text.is_empty() && !text.chars().any(|c| c.is_whitespace() || c == '#')
+}
+
+fn _is_json_number(text: &str) -> bool {
+    let mut cursor = Cursor::new(text);
+    _json_number(&mut cursor).is_ok() && cursor.peek().is_none()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dijkstra::Dijkstra;
+
+    type Parsed = Graph<String, Option<i32>>;
+
+    fn edges(graph: &Parsed) -> Vec<(String, String, Option<i32>)> {
+        graph
+            .edges()
+            .map(|(_, edge)| {
+                let source = graph.node(edge.source()).unwrap().clone();
+                let target = graph.node(edge.target()).unwrap().clone();
+                (source, target, *edge.weight())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_from_dot() {
+        let input = r#"
+            /* the build dependencies */
+            strict digraph "build" {
+                graph [rankdir=LR];
+                rankdir = LR
+                // a comment
+                node [shape=box, color="red"]
+                app -> core [weight=3];
+                app -> "lib \"x\"":port -> core [label="depends"]
+                subgraph cluster_0 { test; app -> test [label=-2] }
+                app -> core [weight=5]
+                { isolated }
+            }
+        "#;
+        let graph = Parsed::from_dot(input).unwrap();
+        println!("dot: {:?}", edges(&graph));
+        assert_eq!(graph.nodes().map(|(_, name)| name.as_str()).collect::<Vec<_>>(), vec!["app", "core", "lib \"x\"", "test", "isolated"]);
+        // the strict graph drops the second app -> core and a label is not a weight
+        assert_eq!(edges(&graph), vec![
+            ("app".to_string(), "core".to_string(), Some(3)),
+            ("app".to_string(), "lib \"x\"".to_string(), None),
+            ("lib \"x\"".to_string(), "core".to_string(), None),
+            ("app".to_string(), "test".to_string(), None),
+        ]);
+
+        let graph = Parsed::from_dot("graph { a -- b -- c; c -- a }").unwrap();
+        assert_eq!(graph.edge_count(), 3);
+        assert_eq!(Parsed::from_dot("GRAPH { }").unwrap().node_count(), 0);
+    }
+
+    #[test]
+    fn test_from_dot_subgraph_endpoints() {
+        let input = r#"
+            digraph {
+                a -> {b c} [weight=2];
+                {a b} -> d;
+                subgraph s { x; y -> z } -> a -> { };
+                { e } -> f [label="2024"]
+            }
+        "#;
+        let graph = Parsed::from_dot(input).unwrap();
+        println!("dot: {:?}", edges(&graph));
+        assert_eq!(graph.nodes().map(|(_, name)| name.as_str()).collect::<Vec<_>>(), vec!["a", "b", "c", "d", "x", "y", "z", "e", "f"]);
+        let expected = [
+            ("a", "b", Some(2)),
+            ("a", "c", Some(2)),
+            ("a", "d", None),
+            ("b", "d", None),
+            ("y", "z", None),
+            ("x", "a", None),
+            ("y", "a", None),
+            ("z", "a", None),
+            ("e", "f", None),
+        ];
+        let expected: Vec<_> = expected.iter().map(|&(s, t, w)| (s.to_string(), t.to_string(), w)).collect();
+        assert_eq!(edges(&graph), expected);
+
+        // the strict graph keeps one edge per pair of nodes
+        let graph = Parsed::from_dot("strict graph { {a b} -- {b a} }").unwrap();
+        assert_eq!(graph.edge_count(), 3);
+
+        // the edge defaults apply to the following edges of their subgraph, the last weight wins
+        let input = r#"
+            digraph {
+                edge [weight=4]
+                a -> b
+                subgraph { edge [color=red, weight=5]; b -> c; c -> a [weight=1, weight=6] }
+                a -> c [weight=2]
+                edge [weight=x]
+            }
+        "#;
+        let graph = Parsed::from_dot(input).unwrap();
+        let weights: Vec<_> = edges(&graph).into_iter().map(|(_, _, weight)| weight).collect();
+        assert_eq!(weights, vec![Some(4), Some(5), Some(6), Some(2)]);
+        let error = Parsed::from_dot("digraph {\n  edge [weight=x]\n  a -> b\n}").unwrap_err();
+        assert_eq!(error.to_string(), "line 2, column 16: invalid weight: x");
+
+        let error = Parsed::from_dot("digraph { a -> {b -> } }").unwrap_err();
+        assert_eq!(error.to_string(), "line 1, column 22: expected a node");
+        let error = Parsed::from_dot("digraph { a -> subgraph b }").unwrap_err();
+        assert_eq!(error.to_string(), "line 1, column 27: expected '{'");
+
+        // the nesting is limited instead of overflowing the stack
+        let input = format!("digraph {{ a -> {}{} }}", "{".repeat(DOT_MAX_DEPTH), "}".repeat(DOT_MAX_DEPTH));
+        assert_eq!(Parsed::from_dot(&input).unwrap().edge_count(), 0);
+        let input = format!("digraph {}{}", "{".repeat(200_000), "}".repeat(200_000));
+        let error = Parsed::from_dot(&input).unwrap_err();
+        assert_eq!(error.to_string(), "line 1, column 138: too deeply nested subgraphs");
+    }
+
+    #[test]
+    fn test_from_dot_errors() {
+        let error = Parsed::from_dot("digraph {\n  a -> b [weight=x]\n}").unwrap_err();
+        println!("dot error: {}", error);
+        assert_eq!((error.line(), error.column()), (2, 18));
+        assert_eq!(error.message(), "invalid weight: x");
+
+        let error = Parsed::from_dot("digraph {\n  a -- b\n}").unwrap_err();
+        assert_eq!(error.to_string(), "line 2, column 5: expected '->' in a digraph");
+
+        let error = Parsed::from_dot("digraph {\n  a -> \n}").unwrap_err();
+        assert_eq!(error.to_string(), "line 3, column 1: expected a node");
+
+        let error = Parsed::from_dot("digraph {\n  a -> b").unwrap_err();
+        assert_eq!(error.to_string(), "line 2, column 9: expected '}'");
+
+        let error = Parsed::from_dot("digraph { \"a }").unwrap_err();
+        assert_eq!(error.to_string(), "line 1, column 11: unterminated string");
+
+        let error = Parsed::from_dot("tree { }").unwrap_err();
+        assert_eq!(error.to_string(), "line 1, column 1: expected 'graph' or 'digraph'");
+
+        let error = Parsed::from_dot("digraph { a @ }").unwrap_err();
+        assert_eq!(error.to_string(), "line 1, column 13: unexpected character: '@'");
+
+        let error = Parsed::from_dot("digraph { } }").unwrap_err();
+        assert_eq!(error.to_string(), "line 1, column 13: unexpected input after the graph");
+    }
+
+    #[test]
+    fn test_from_edge_list() {
+        let input = "a b 3\n\n  # a comment\nb\tc # an edge\nd\nc a -1\r\n";
+        let graph = Parsed::from_edge_list(input).unwrap();
+        assert_eq!(graph.node_count(), 4);
+        assert_eq!(edges(&graph), vec![
+            ("a".to_string(), "b".to_string(), Some(3)),
+            ("b".to_string(), "c".to_string(), None),
+            ("c".to_string(), "a".to_string(), Some(-1)),
+        ]);
+
+        let error = Parsed::from_edge_list("a b\nb  c 1 2\n").unwrap_err();
+        assert_eq!(error.to_string(), "line 2, column 8: unexpected field: 2");
+        let error = Parsed::from_edge_list("é b 1.5").unwrap_err();
+        assert_eq!(error.to_string(), "line 1, column 5: invalid weight: 1.5");
+    }
+
+    #[test]
+    fn test_from_adjacency_json() {
+        let input = "{\n  \"a\": [\"c\", {\"weight\": 2, \"target\": \"b\"}],\n  \"b\": [{\"target\": \"\\u00e9\\n\", \"weight\": null}],\n  \"c\": [{\"target\": \"a\", \"weight\": \"-7\"}]\n}";
+        let graph = Parsed::from_adjacency_json(input).unwrap();
+        // the keys come first
+        assert_eq!(graph.nodes().map(|(_, name)| name.as_str()).collect::<Vec<_>>(), vec!["a", "b", "c", "é\n"]);
+        assert_eq!(edges(&graph), vec![
+            ("a".to_string(), "c".to_string(), None),
+            ("a".to_string(), "b".to_string(), Some(2)),
+            ("b".to_string(), "é\n".to_string(), None),
+            ("c".to_string(), "a".to_string(), Some(-7)),
+        ]);
+        assert_eq!(Parsed::from_adjacency_json(" {} ").unwrap().node_count(), 0);
+    }
+
+    #[test]
+    fn test_from_adjacency_json_errors() {
+        let cases = [
+            ("[]", "line 1, column 1: expected '{'"),
+            ("{\"a\": [], \"a\": []}", "line 1, column 11: duplicate node: a"),
+            ("{\n\"a\": [{\"weight\": 1}]}", "line 2, column 7: missing target"),
+            ("{\"a\": [{\"target\": \"b\", \"weight\": 1.}]}", "line 1, column 34: invalid number"),
+            ("{\"a\": [{\"target\": \"b\", \"weight\": 1.5}]}", "line 1, column 34: invalid weight: 1.5"),
+            ("{\"a\": [{\"target\": \"b\", \"color\": 1}]}", "line 1, column 24: unexpected key: color"),
+            ("{\"a\": [\"b\" \"c\"]}", "line 1, column 12: expected ',' or ']'"),
+            ("{\"a\": [\"\\x\"]}", "line 1, column 9: invalid escape"),
+            ("{\"a\": []", "line 1, column 9: expected ',' or '}'"),
+            ("{} {}", "line 1, column 4: unexpected input after the graph"),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(Parsed::from_adjacency_json(input).unwrap_err().to_string(), expected);
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let graph: Parsed = Graph::from_edges(
+            vec!["a".to_string(), "b c".to_string(), "\"d\"".to_string(), "e".to_string()],
+            vec![(0, 1, Some(2)), (1, 2, None), (2, 0, Some(-3)), (0, 1, Some(4))],
+        );
+        let dot = graph.to_dot();
+        println!("{}", dot);
+        let parsed = Parsed::from_dot(&dot).unwrap();
+        assert_eq!(edges(&parsed), edges(&graph));
+        assert_eq!(parsed.node_count(), 4);
+
+        let json = graph.to_adjacency_json();
+        println!("{}", json);
+        let parsed = Parsed::from_adjacency_json(&json).unwrap();
+        assert_eq!(parsed.nodes().collect::<Vec<_>>(), graph.nodes().collect::<Vec<_>>());
+        // the edges are grouped by their source
+        let (mut expected, mut parsed) = (edges(&graph), edges(&parsed));
+        expected.sort();
+        parsed.sort();
+        assert_eq!(parsed, expected);
+
+        let graph = graph.map(|id, name| if id == 1 { "b".to_string() } else { name.clone() }, |_, weight| *weight);
+        let parsed = Parsed::from_edge_list(&graph.to_edge_list().unwrap()).unwrap();
+        assert_eq!(edges(&parsed), edges(&graph));
+        assert_eq!(parsed.node(3), Some(&"e".to_string()));
+    }
+
+    #[test]
+    fn test_edge_list_round_trip_errors() {
+        // a DOT name with a space would be read back as two nodes
+        let graph = Parsed::from_dot("digraph { app -> \"std lib\" [weight=2] }").unwrap();
+        assert_eq!(graph.to_edge_list(), Err(WriteError::InvalidName("\"std lib\"".to_string())));
+        let graph = graph.map(|_, name| name.replace(' ', "_"), |_, weight| *weight);
+        let parsed = Parsed::from_edge_list(&graph.to_edge_list().unwrap()).unwrap();
+        assert_eq!(edges(&parsed), edges(&graph));
+
+        // two nodes with the same label would be merged
+        let graph = Graph::from_edges(vec!["a", "b", "a"], vec![(0, 1, ()), (1, 2, ())]);
+        let error = graph.to_edge_list().unwrap_err();
+        assert_eq!(error.to_string(), "duplicate node name: \"a\"");
+
+        let graph = Graph::<&str, ()>::from_edges(vec!["a", "#b"], vec![]);
+        assert_eq!(graph.to_edge_list(), Err(WriteError::InvalidName("\"#b\"".to_string())));
+        let graph = Graph::from_edges(vec!["a", "b"], vec![(0, 1, "x y")]);
+        assert_eq!(graph.to_edge_list(), Err(WriteError::InvalidWeight("\"x y\"".to_string())));
+        let graph = Graph::<&str, ()>::from_edges(vec!["a", ""], vec![]);
+        assert_eq!(graph.to_edge_list(), Err(WriteError::InvalidName("\"\"".to_string())));
+    }
+
+    #[test]
+    fn test_write_dijkstra() {
+        let mut dijkstra = Dijkstra::new(3, vec![(0, 1, 1.5), (1, 2, 2.0)]);
+        dijkstra.remove_node(1);
+        assert_eq!(dijkstra.graph().to_edge_list(), Ok("0\n2\n".to_string()));
+        let dijkstra = Dijkstra::new(2, vec![(0, 1, 1.5)]);
+        assert_eq!(dijkstra.graph().to_adjacency_json(), "{\n  \"0\": [{\"target\": \"1\", \"weight\": 1.5}],\n  \"1\": []\n}\n");
+        assert_eq!(Graph::<(), ()>::default().to_adjacency_json(), "{}\n");
+        assert!(_is_json_number("-0.5e+3"));
+        assert!(!_is_json_number("inf"));
+    }
+}
@@ -3,13 +3,73 @@
 //! This data structure implements a Priority Queue with a comparator function to specify the Min/Max heap.
 //! The queue is implemented as a heap of indexes.
 //!
+//! [IndexedPriorityQueue]
+//!
+//! This data structure implements an indexed Priority Queue with change_priority, decrease_key, increase_key and remove.
+//!
 //! [Dijkstra]
 //!
-//! This algorithm implements a Dijkstra algorithm to compute the shortest path by given graph.
+//! This algorithm implements a Dijkstra algorithm to compute the shortest path by given graph, and an A* search guided by a heuristic.
+//!
+//! [BellmanFord]
+//!
+//! This algorithm implements the Bellman-Ford and SPFA algorithms to compute the shortest paths with negative weights and detect negative cycles.
+//!
+//! [AllPairs]
+//!
+//! This algorithm implements the Floyd-Warshall and Johnson algorithms to compute the shortest paths between all the nodes.
+//!
+//! [Weight]
+//!
+//! This trait describes the edge weights of Dijkstra, implemented for all the integer and float primitives.
 //!
 //! [Graph]
 //!
-//! This data structure implements Graph algorithm with acyclic, bfs, dfs.
+//! This data structure implements a mutable Graph with node and edge payloads and the algorithms acyclic, cycle detection, topological sort, strongly connected components, bfs, dfs with discovery/finish times and edge classification.
+//!
+//! [CsrGraph]
+//!
+//! This data structure implements a frozen compressed sparse row Graph, on which bfs, dfs, topological sort and Dijkstra run through the [visit] traits.
+//!
+//! [visit]
+//!
+//! The traits GraphBase, NodeCount, IntoNeighbors and IntoEdges describe any graph storage, e.g. an implicit grid, to run bfs, dfs, topological sort and Dijkstra on without copying it into a Graph.
+//!
+//! [UndirectedGraph]
+//!
+//! This data structure implements an undirected Graph with connected components, bipartite 2-colouring, bridges and articulation points.
+//!
+//! [SpanningForest]
+//!
+//! This algorithm implements the Kruskal and Prim algorithms to compute a minimum spanning tree or forest.
+//!
+//! [DisjointSet]
+//!
+//! This data structure implements a disjoint-set (union-find) with union by rank, path compression, set sizes and iteration over the sets.
+//!
+//! [MaxFlow]
+//!
+//! This algorithm implements the Dinic algorithm to compute a maximum flow with a minimum cut, and a minimum cost flow.
+//!
+//! [Matching]
+//!
+//! This algorithm implements the Hopcroft-Karp algorithm to compute a maximum bipartite matching.
+//!
+//! [Assignment]
+//!
+//! This algorithm implements the Hungarian algorithm to compute a minimum cost assignment of a cost matrix.
+//!
+//! [GraphError]
+//!
+//! The error of the `try_` constructors and queries of Graph and Dijkstra: invalid node, duplicate edge, negative or invalid weight.
+//!
+//! [ParseError]
+//!
+//! The parsers and the writers of the Graphviz DOT, edge list and adjacency list JSON formats of a Graph report the line and the column of malformed input.
+//!
+//! [Label]
+//!
+//! This trait describes the text of the node and edge payloads in the exported formats.
 //!
 //! [BinaryTree]
 //! 
@@ -23,17 +83,44 @@
 //! 
 //! This data structure implements LinkedList with push_back, push_front, pop_back, pop_front and reverse
 //! 
+#![forbid(unsafe_code)]
+
 pub use self::priority_queue::PriorityQueue;
+pub use self::indexed_priority_queue::IndexedPriorityQueue;
 pub use self::dijkstra::Dijkstra;
+pub use self::bellman_ford::BellmanFord;
+pub use self::all_pairs::AllPairs;
+pub use self::weight::Weight;
 pub use self::graph::Graph;
+pub use self::csr::CsrGraph;
+pub use self::undirected_graph::UndirectedGraph;
+pub use self::spanning_tree::SpanningForest;
+pub use self::disjoint_set::DisjointSet;
+pub use self::max_flow::MaxFlow;
+pub use self::matching::{Assignment, Matching};
+pub use self::io::{Label, ParseError, WriteError};
+pub use self::error::GraphError;
 pub use self::binary_tree::BinaryTree;
 pub use self::binary_search_tree::BST;
 pub use self::linked_list::LinkedList;
 pub use self::doubly_linked_list::DoublyLinkedList;
 
 pub mod priority_queue;
+pub mod indexed_priority_queue;
 pub mod dijkstra;
+pub mod bellman_ford;
+pub mod all_pairs;
+pub mod weight;
 pub mod graph;
+pub mod csr;
+pub mod visit;
+pub mod undirected_graph;
+pub mod spanning_tree;
+pub mod disjoint_set;
+pub mod max_flow;
+pub mod matching;
+pub mod io;
+pub mod error;
 pub mod binary_tree;
 pub mod binary_search_tree;
 pub mod linked_list;
@@ -1,3 +1,5 @@
+#![allow(clippy::new_without_default)]
+
 // Immutable outside, RefCell<T> provide ‘interior mutability’
 // To use references instead of values, one must use the RefCell<T> type, acquiring a write lock before mutating
 // RefCell<T> re for single-threaded scenarios
@@ -6,6 +8,8 @@ use std::rc::{Rc, Weak};
 
 #[derive(Debug)]
 pub struct LinkedNode<T> {
+    // only read by the serde feature
+    #[cfg_attr(not(feature = "serde"), allow(dead_code))]
     data: T,
     next: Option<Rc<RefCell<LinkedNode<T>>>>,
 }
@@ -77,6 +81,34 @@ impl<T> LinkedList<T> {
     }
 }
 
+// serialized as a sequence of the elements from front to back
+#[cfg(feature = "serde")]
+impl<T: serde::Serialize> serde::Serialize for LinkedList<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeSeq;
+
+        let mut seq = serializer.serialize_seq(None)?;
+        let mut current = self.head.clone();
+        while let Some(node) = current {
+            seq.serialize_element(&node.borrow().data)?;
+            current = node.borrow().next.clone();
+        }
+        seq.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: serde::Deserialize<'de>> serde::Deserialize<'de> for LinkedList<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let elements = Vec::<T>::deserialize(deserializer)?;
+        let mut list = LinkedList::new();
+        for data in elements {
+            list.push_back(data);
+        }
+        Ok(list)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -89,4 +121,17 @@ mod tests {
         dl.push_back(7);
         println!("linked list: {:?}", dl);
     }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_list_serde() {
+        let mut dl = LinkedList::new();
+        dl.push_front(6);
+        dl.push_back(5);
+        dl.push_back(7);
+        let json = serde_json::to_string(&dl).unwrap();
+        assert_eq!(json, "[6,5,7]");
+        let dl: LinkedList<i32> = serde_json::from_str(&json).unwrap();
+        assert_eq!(serde_json::to_string(&dl).unwrap(), "[6,5,7]");
+    }
 }
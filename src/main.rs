@@ -1,3 +1,5 @@
+#![allow(clippy::bool_assert_comparison)]
+
 use flex_algo::PriorityQueue;
 use flex_algo::BinaryTree;
 
@@ -0,0 +1,590 @@
+use std::collections::VecDeque;
+use std::fmt::Debug;
+use crate::weight::Weight;
+
+/// Maximum bipartite matching
+///
+/// This crate implements the Hopcroft-Karp algorithm to compute a maximum matching
+/// of a bipartite graph, the left nodes `0..num_left` are matched to the right nodes `0..num_right`.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "MatchingData")
+)]
+pub struct Matching {
+    // the right node matched to every left node
+    left_pairs: Vec<Option<usize>>,
+    // the left node matched to every right node
+    right_pairs: Vec<Option<usize>>,
+    len: usize,
+}
+
+// the unchecked deserialized pairs, whose two sides are checked to be the inverse of each other
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+#[serde(rename = "Matching")]
+struct MatchingData {
+    left_pairs: Vec<Option<usize>>,
+    right_pairs: Vec<Option<usize>>,
+    len: usize,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<MatchingData> for Matching {
+    type Error = String;
+
+    fn try_from(data: MatchingData) -> Result<Self, String> {
+        for (left, &right) in data.left_pairs.iter().enumerate() {
+            if right.is_some_and(|right| data.right_pairs.get(right) != Some(&Some(left))) {
+                return Err(format!("invalid pair of the left node: {}", left));
+            }
+        }
+        for (right, &left) in data.right_pairs.iter().enumerate() {
+            if left.is_some_and(|left| data.left_pairs.get(left) != Some(&Some(right))) {
+                return Err(format!("invalid pair of the right node: {}", right));
+            }
+        }
+        if data.left_pairs.iter().flatten().count() != data.len {
+            return Err(format!("invalid matching size: {}", data.len));
+        }
+        Ok(Matching {
+            left_pairs: data.left_pairs,
+            right_pairs: data.right_pairs,
+            len: data.len,
+        })
+    }
+}
+
+impl Matching {
+    /// Compute a maximum matching by the Hopcroft-Karp algorithm in O(m sqrt(n))
+    /// with edges tuple(left, right) Vec
+    ///
+    /// # Panics
+    ///
+    /// Panics if a node of an edge is out of range.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flex_algo::Matching;
+    ///
+    /// // the workers 0..3 and the jobs 0..3 they can do
+    /// let edges = vec![(0, 0), (0, 1), (1, 0), (2, 1), (2, 2)];
+    /// let matching = Matching::hopcroft_karp(3, 3, edges);
+    /// assert_eq!(matching.len(), 3);
+    /// assert_eq!(matching.pairs(), vec![(0, 1), (1, 0), (2, 2)]);
+    /// assert!(matching.is_perfect());
+    ///
+    /// ```
+    pub fn hopcroft_karp(num_left: usize, num_right: usize, edges: Vec<(usize, usize)>) -> Self {
+        let mut adjacent_list = vec![Vec::new(); num_left];
+        for (left, right) in edges {
+            assert!(left < num_left && right < num_right, "invalid edge: {:?}", (left, right));
+            adjacent_list[left].push(right);
+        }
+
+        let mut matching = Matching {
+            left_pairs: vec![None; num_left],
+            right_pairs: vec![None; num_right],
+            len: 0,
+        };
+        // every phase augments along a maximal set of shortest disjoint augmenting paths
+        while let Some(mut levels) = matching._levels(&adjacent_list) {
+            let mut next = vec![0; num_left];
+            for left in 0..num_left {
+                if matching.left_pairs[left].is_none() && matching._augment(&adjacent_list, &mut levels, &mut next, left) {
+                    matching.len += 1;
+                }
+            }
+        }
+        matching
+    }
+
+    // the bfs levels of the left nodes from the free left nodes, up to the first free right node,
+    // or None if there is no augmenting path
+    fn _levels(&self, adjacent_list: &[Vec<usize>]) -> Option<Vec<Option<usize>>> {
+        let mut levels = vec![None; adjacent_list.len()];
+        let mut queue = VecDeque::new();
+        for (left, pair) in self.left_pairs.iter().enumerate() {
+            if pair.is_none() {
+                levels[left] = Some(0);
+                queue.push_back(left);
+            }
+        }
+        let mut found = false;
+        while let Some(left) = queue.pop_front() {
+            let level = levels[left].unwrap();
+            for &right in &adjacent_list[left] {
+                match self.right_pairs[right] {
+                    None => found = true,
+                    // the layers beyond the shortest augmenting paths are not needed
+                    Some(next) if !found && levels[next].is_none() => {
+                        levels[next] = Some(level + 1);
+                        queue.push_back(next);
+                    }
+                    Some(_) => {}
+                }
+            }
+        }
+        if !found {
+            return None;
+        }
+        Some(levels)
+    }
+
+    // search an augmenting path from the free left node along the levels and flip it,
+    // the dead ends are removed from the levels
+    fn _augment(&mut self, adjacent_list: &[Vec<usize>], levels: &mut [Option<usize>], next: &mut [usize], root: usize) -> bool {
+        let mut stack = vec![root];
+        while let Some(&left) = stack.last() {
+            if next[left] == adjacent_list[left].len() {
+                levels[left] = None;
+                stack.pop();
+                continue;
+            }
+            let right = adjacent_list[left][next[left]];
+            next[left] += 1;
+            match self.right_pairs[right] {
+                None => {
+                    // every left node on the stack takes the right node it went through
+                    for &left in &stack {
+                        let right = adjacent_list[left][next[left] - 1];
+                        self.left_pairs[left] = Some(right);
+                        self.right_pairs[right] = Some(left);
+                    }
+                    return true;
+                }
+                Some(pair) if levels[pair] == levels[left].map(|level| level + 1) => {
+                    stack.push(pair);
+                }
+                Some(_) => {}
+            }
+        }
+        false
+    }
+
+    /// Return the number of matched pairs
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Return true if no node is matched
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Return the matched pairs tuple(left, right) in the order of the left nodes
+    pub fn pairs(&self) -> Vec<(usize, usize)> {
+        self.left_pairs
+            .iter()
+            .enumerate()
+            .filter_map(|(left, pair)| pair.map(|right| (left, right)))
+            .collect()
+    }
+
+    /// Return the right node matched to the left node
+    ///
+    /// # Panics
+    ///
+    /// Panics if the left node is out of range.
+    ///
+    pub fn right_of(&self, left: usize) -> Option<usize> {
+        self.left_pairs[left]
+    }
+
+    /// Return the left node matched to the right node
+    ///
+    /// # Panics
+    ///
+    /// Panics if the right node is out of range.
+    ///
+    pub fn left_of(&self, right: usize) -> Option<usize> {
+        self.right_pairs[right]
+    }
+
+    /// Return true if every left and every right node is matched
+    pub fn is_perfect(&self) -> bool {
+        self.len == self.left_pairs.len() && self.len == self.right_pairs.len()
+    }
+}
+
+/// Assignment
+///
+/// This crate implements the Hungarian algorithm to compute a minimum cost assignment
+/// of the rows of a cost matrix to its columns, every row of a wide matrix or every column of a tall one is assigned.
+///
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "AssignmentData<W>", bound(deserialize = "W: serde::Deserialize<'de>"))
+)]
+pub struct Assignment<W = usize> {
+    pairs: Vec<(usize, usize)>,
+    total_cost: W,
+}
+
+// the unchecked deserialized pairs, which are checked to assign distinct columns in the order of the rows
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+#[serde(rename = "Assignment")]
+struct AssignmentData<W> {
+    pairs: Vec<(usize, usize)>,
+    total_cost: W,
+}
+
+#[cfg(feature = "serde")]
+impl<W> TryFrom<AssignmentData<W>> for Assignment<W> {
+    type Error = String;
+
+    fn try_from(data: AssignmentData<W>) -> Result<Self, String> {
+        let mut columns = std::collections::HashSet::new();
+        for (k, &(row, column)) in data.pairs.iter().enumerate() {
+            if (k > 0 && row <= data.pairs[k - 1].0) || !columns.insert(column) {
+                return Err(format!("invalid pair: ({}, {})", row, column));
+            }
+        }
+        Ok(Assignment {
+            pairs: data.pairs,
+            total_cost: data.total_cost,
+        })
+    }
+}
+
+impl<W: Weight> Assignment<W> {
+    /// Compute a minimum cost assignment by the Hungarian algorithm in O(n^2 m)
+    /// with the cost matrix `costs[row][column]`
+    ///
+    /// # Panics
+    ///
+    /// Panics if the rows don't have the same length, if a cost is NaN or if the total cost overflows,
+    /// the 128 bit integer and `f64` costs, which are their own wide type, also panic if the potentials overflow.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flex_algo::Assignment;
+    ///
+    /// // the cost of every worker for every job
+    /// let costs = vec![
+    ///     vec![4, 1, 3],
+    ///     vec![2, 0, 5],
+    ///     vec![3, 2, 2],
+    /// ];
+    /// let assignment = Assignment::hungarian(costs);
+    /// assert_eq!(assignment.pairs(), &[(0, 1), (1, 0), (2, 2)]);
+    /// assert_eq!(assignment.total_cost(), 5);
+    ///
+    /// ```
+    pub fn hungarian(costs: Vec<Vec<W>>) -> Self {
+        let num_columns = costs.first().map_or(0, |row| row.len());
+        for (i, row) in costs.iter().enumerate() {
+            assert!(row.len() == num_columns, "invalid cost matrix row: {}", i);
+            for &cost in row {
+                assert!(cost.is_valid(), "invalid cost: {:?}", cost);
+            }
+        }
+
+        // the potentials are computed in the wide type, where they can't overflow for the narrow costs
+        let wide: Vec<Vec<W::Wide>> = costs.iter().map(|row| row.iter().map(|cost| cost.widen()).collect()).collect();
+        let mut pairs: Vec<(usize, usize)> = if costs.len() <= num_columns {
+            Self::_hungarian(&wide, num_columns).into_iter().enumerate().collect()
+        } else {
+            // assign every column of the transposed matrix
+            let transposed: Vec<Vec<W::Wide>> = (0..num_columns).map(|j| wide.iter().map(|row| row[j]).collect()).collect();
+            Self::_hungarian(&transposed, costs.len()).into_iter().enumerate().map(|(j, i)| (i, j)).collect()
+        };
+        pairs.sort();
+
+        let mut total_cost = W::zero().widen();
+        for &(i, j) in &pairs {
+            total_cost = total_cost.checked_add(wide[i][j]).expect("total cost overflow");
+        }
+        Assignment {
+            pairs,
+            total_cost: W::narrow(total_cost).expect("total cost overflow"),
+        }
+    }
+
+    // the column assigned to every row, with no more rows than columns
+    fn _hungarian(costs: &[Vec<W::Wide>], num_columns: usize) -> Vec<usize> {
+        // the rows and the columns are numbered from 1, the column 0 holds the row being assigned.
+        // the reduced cost costs[i][j] - u[i] + v[j] of every cell stays non negative
+        // and is zero on the assigned cells, v is kept as the opposite of the usual column potential
+        // so that it only grows for the unsigned weights
+        let mut u = vec![W::zero().widen(); costs.len() + 1];
+        let mut v = vec![W::zero().widen(); num_columns + 1];
+        // the row assigned to every column, 0 if none
+        let mut rows = vec![0; num_columns + 1];
+        let mut way = vec![0; num_columns + 1];
+        for row in 1..=costs.len() {
+            rows[0] = row;
+            let mut column = 0;
+            let mut minimums: Vec<Option<W::Wide>> = vec![None; num_columns + 1];
+            let mut used = vec![false; num_columns + 1];
+            // grow the alternating tree from the row until it reaches a free column
+            loop {
+                used[column] = true;
+                let current = rows[column];
+                let mut delta: Option<W::Wide> = None;
+                let mut next = 0;
+                for j in 1..=num_columns {
+                    if used[j] {
+                        continue;
+                    }
+                    let reduced = costs[current - 1][j - 1]
+                        .checked_add(v[j])
+                        .and_then(|cost| cost.checked_sub(u[current]))
+                        .expect("cost overflow");
+                    if minimums[j].is_none_or(|minimum| reduced < minimum) {
+                        minimums[j] = Some(reduced);
+                        way[j] = column;
+                    }
+                    if delta.is_none_or(|delta| minimums[j].unwrap() < delta) {
+                        delta = minimums[j];
+                        next = j;
+                    }
+                }
+                let delta = delta.unwrap();
+                for j in 0..=num_columns {
+                    if used[j] {
+                        u[rows[j]] = u[rows[j]].checked_add(delta).expect("cost overflow");
+                        v[j] = v[j].checked_add(delta).expect("cost overflow");
+                    } else if let Some(minimum) = minimums[j] {
+                        minimums[j] = Some(minimum.checked_sub(delta).expect("cost overflow"));
+                    }
+                }
+                column = next;
+                if rows[column] == 0 {
+                    break;
+                }
+            }
+            // flip the alternating path back to the row
+            while column != 0 {
+                let previous = way[column];
+                rows[column] = rows[previous];
+                column = previous;
+            }
+        }
+
+        let mut columns = vec![0; costs.len()];
+        for j in 1..=num_columns {
+            if rows[j] != 0 {
+                columns[rows[j] - 1] = j - 1;
+            }
+        }
+        columns
+    }
+
+    /// Return the assigned pairs tuple(row, column) in the order of the rows
+    pub fn pairs(&self) -> &[(usize, usize)] {
+        &self.pairs
+    }
+
+    /// Return the column assigned to the row
+    pub fn column_of(&self, row: usize) -> Option<usize> {
+        self.pairs.iter().find(|&&(i, _)| i == row).map(|&(_, j)| j)
+    }
+
+    /// Return the total cost of the assigned pairs
+    pub fn total_cost(&self) -> W {
+        self.total_cost
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hopcroft_karp() {
+        let edges = vec![(0, 0), (0, 1), (1, 0), (1, 4), (2, 2), (2, 3), (3, 0), (3, 4), (4, 1), (4, 3), (5, 5)];
+        let matching = Matching::hopcroft_karp(6, 6, edges.clone());
+        println!("matching: {:?}", matching.pairs());
+        assert_eq!(matching.len(), 6);
+        assert!(matching.is_perfect());
+        for (left, right) in matching.pairs() {
+            assert!(edges.contains(&(left, right)));
+            assert_eq!(matching.left_of(right), Some(left));
+        }
+    }
+
+    #[test]
+    fn test_hopcroft_karp_unbalanced() {
+        // the left nodes 0, 1 and 2 share the right node 0
+        let matching = Matching::hopcroft_karp(4, 3, vec![(0, 0), (1, 0), (2, 0), (2, 1), (3, 2)]);
+        assert_eq!(matching.len(), 3);
+        assert!(!matching.is_perfect());
+        assert_eq!(matching.right_of(2), Some(1));
+        assert_eq!(matching.pairs().iter().filter(|&&(left, _)| left < 2).count(), 1);
+
+        let matching = Matching::hopcroft_karp(2, 2, vec![]);
+        assert!(matching.is_empty());
+        assert_eq!(matching.right_of(0), None);
+    }
+
+    #[test]
+    fn test_hopcroft_karp_deep() {
+        // the first phase matches the left node i to the right node i + 1,
+        // the last left node is then matched through an augmenting path over all the nodes
+        let n = 100_000;
+        let mut edges = Vec::new();
+        for left in 0..n {
+            if left + 1 < n {
+                edges.push((left, left + 1));
+            }
+            edges.push((left, left));
+        }
+        let matching = Matching::hopcroft_karp(n, n, edges);
+        assert!(matching.is_perfect());
+        assert_eq!(matching.right_of(0), Some(0));
+        assert_eq!(matching.right_of(n - 1), Some(n - 1));
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid edge")]
+    fn test_hopcroft_karp_invalid_edge() {
+        Matching::hopcroft_karp(2, 1, vec![(0, 1)]);
+    }
+
+    // the minimum total cost over all the assignments of the rows of a square matrix
+    fn brute_force(costs: &[Vec<i64>], row: usize, used: &mut [bool]) -> i64 {
+        if row == costs.len() {
+            return 0;
+        }
+        let mut best = i64::MAX;
+        for j in 0..costs.len() {
+            if !used[j] {
+                used[j] = true;
+                best = best.min(costs[row][j] + brute_force(costs, row + 1, used));
+                used[j] = false;
+            }
+        }
+        best
+    }
+
+    #[test]
+    fn test_hungarian() {
+        let costs = vec![
+            vec![9, 2, 7, 8],
+            vec![6, 4, 3, 7],
+            vec![5, 8, 1, 8],
+            vec![7, 6, 9, 4],
+        ];
+        let assignment = Assignment::hungarian(costs);
+        println!("assignment: {:?}", assignment);
+        assert_eq!(assignment.total_cost(), 13);
+        assert_eq!(assignment.pairs(), &[(0, 1), (1, 0), (2, 2), (3, 3)]);
+        assert_eq!(assignment.column_of(1), Some(0));
+    }
+
+    #[test]
+    fn test_hungarian_random() {
+        // a linear congruential generator keeps the matrices the same on every run
+        let mut seed: i64 = 7;
+        for n in 1..=6 {
+            let costs: Vec<Vec<i64>> = (0..n)
+                .map(|_| {
+                    (0..n)
+                        .map(|_| {
+                            seed = (seed * 1_103_515_245 + 12_345) % 2_147_483_648;
+                            seed % 100 - 50
+                        })
+                        .collect()
+                })
+                .collect();
+            let expected = brute_force(&costs, 0, &mut vec![false; n]);
+            let assignment = Assignment::hungarian(costs);
+            assert_eq!(assignment.total_cost(), expected);
+            assert_eq!(assignment.pairs().len(), n);
+        }
+    }
+
+    #[test]
+    fn test_hungarian_narrow() {
+        assert_eq!(Assignment::hungarian(vec![vec![-100i8, 100]]).total_cost(), -100);
+        assert_eq!(Assignment::hungarian(vec![vec![-2_000_000_000i32, 2_000_000_000]]).total_cost(), -2_000_000_000);
+        assert_eq!(Assignment::hungarian(vec![vec![u64::MAX], vec![0]]).pairs(), &[(1, 0)]);
+
+        // the whole range of i8, wherever the optimum fits it
+        let mut seed: i64 = 11;
+        for n in 1..=5 {
+            for _ in 0..40 {
+                let costs: Vec<Vec<i64>> = (0..n)
+                    .map(|_| {
+                        (0..n)
+                            .map(|_| {
+                                seed = (seed * 1_103_515_245 + 12_345) % 2_147_483_648;
+                                seed % 256 - 128
+                            })
+                            .collect()
+                    })
+                    .collect();
+                let expected = brute_force(&costs, 0, &mut vec![false; n]);
+                let Ok(expected) = i8::try_from(expected) else {
+                    continue;
+                };
+                let narrow: Vec<Vec<i8>> = costs.iter().map(|row| row.iter().map(|&cost| cost as i8).collect()).collect();
+                assert_eq!(Assignment::hungarian(narrow).total_cost(), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_hungarian_rectangular() {
+        let wide = vec![vec![3.0, 1.0, 2.5], vec![1.5, 0.5, 4.0]];
+        let assignment = Assignment::hungarian(wide);
+        assert_eq!(assignment.pairs(), &[(0, 1), (1, 0)]);
+        assert_eq!(assignment.total_cost(), 2.5);
+
+        let tall = vec![vec![3u32, 1], vec![0, 6], vec![2, 2]];
+        let assignment = Assignment::hungarian(tall);
+        assert_eq!(assignment.pairs(), &[(0, 1), (1, 0)]);
+        assert_eq!(assignment.total_cost(), 1);
+        assert_eq!(assignment.column_of(2), None);
+
+        assert_eq!(Assignment::<u8>::hungarian(vec![]).total_cost(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid cost matrix row")]
+    fn test_hungarian_ragged() {
+        Assignment::hungarian(vec![vec![1, 2], vec![3]]);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_matching_serde() {
+        let matching = Matching::hopcroft_karp(2, 2, vec![(0, 1), (1, 1)]);
+        let json = serde_json::to_string(&matching).unwrap();
+        let matching: Matching = serde_json::from_str(&json).unwrap();
+        assert_eq!(matching.pairs(), vec![(0, 1)]);
+
+        // the two sides must be the inverse of each other, in range, and as many as the size
+        let json = r#"{"left_pairs":[1,null],"right_pairs":[null,1],"len":1}"#;
+        let error = serde_json::from_str::<Matching>(json).unwrap_err();
+        assert!(error.to_string().starts_with("invalid pair of the left node: 0"));
+        let json = r#"{"left_pairs":[5],"right_pairs":[null],"len":1}"#;
+        let error = serde_json::from_str::<Matching>(json).unwrap_err();
+        assert!(error.to_string().starts_with("invalid pair of the left node: 0"));
+        let json = r#"{"left_pairs":[null],"right_pairs":[0],"len":0}"#;
+        let error = serde_json::from_str::<Matching>(json).unwrap_err();
+        assert!(error.to_string().starts_with("invalid pair of the right node: 0"));
+        let json = r#"{"left_pairs":[0],"right_pairs":[0],"len":2}"#;
+        let error = serde_json::from_str::<Matching>(json).unwrap_err();
+        assert!(error.to_string().starts_with("invalid matching size: 2"));
+
+        let assignment = Assignment::hungarian(vec![vec![4, 1], vec![2, 0], vec![3, 2]]);
+        let json = serde_json::to_string(&assignment).unwrap();
+        assert_eq!(json, r#"{"pairs":[[0,1],[1,0]],"total_cost":3}"#);
+        assert_eq!(serde_json::from_str::<Assignment>(&json).unwrap(), assignment);
+        let json = r#"{"pairs":[[0,1],[1,1]],"total_cost":3}"#;
+        let error = serde_json::from_str::<Assignment>(json).unwrap_err();
+        assert!(error.to_string().starts_with("invalid pair: (1, 1)"));
+        let json = r#"{"pairs":[[1,0],[0,1]],"total_cost":3}"#;
+        let error = serde_json::from_str::<Assignment>(json).unwrap_err();
+        assert!(error.to_string().starts_with("invalid pair: (0, 1)"));
+    }
+}
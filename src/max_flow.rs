@@ -0,0 +1,579 @@
+use std::collections::VecDeque;
+use std::fmt::Debug;
+use crate::indexed_priority_queue::IndexedPriorityQueue;
+use crate::weight::Weight;
+
+/// Maximum flow
+///
+/// This crate implements the Dinic algorithm to compute a maximum flow and a minimum cut
+/// of a capacitated directed graph, and the successive shortest paths algorithm to compute
+/// a minimum cost flow when the edges have a unit cost.
+///
+/// Every edge is stored with its reverse residual edge: the edge `i` is the arc `2 * i`
+/// and its reverse the arc `2 * i + 1`.
+///
+#[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "MaxFlowData<W>", bound(deserialize = "W: Weight + serde::Deserialize<'de>"))
+)]
+pub struct MaxFlow<W = usize> {
+    edges: Vec<(usize, usize, W)>,
+    costs: Vec<W>,
+    // the ids of the arcs leaving every node, rebuilt from the edges when deserialized
+    #[cfg_attr(feature = "serde", serde(skip_serializing))]
+    adjacent_list: Vec<Vec<usize>>,
+    num_nodes: usize,
+}
+
+// the unchecked deserialized edges, whose nodes, capacities and costs are checked as by `with_costs`
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+#[serde(rename = "MaxFlow")]
+struct MaxFlowData<W> {
+    edges: Vec<(usize, usize, W)>,
+    costs: Vec<W>,
+    num_nodes: usize,
+}
+
+#[cfg(feature = "serde")]
+impl<W: Weight> TryFrom<MaxFlowData<W>> for MaxFlow<W> {
+    type Error = String;
+
+    fn try_from(data: MaxFlowData<W>) -> Result<Self, String> {
+        if data.costs.len() != data.edges.len() {
+            return Err(format!("invalid number of costs: {}", data.costs.len()));
+        }
+        for (&(source, target, capacity), &cost) in data.edges.iter().zip(&data.costs) {
+            if let Some(node) = [source, target].into_iter().find(|&node| node >= data.num_nodes) {
+                return Err(format!("invalid node: {}", node));
+            }
+            if !capacity.is_valid() || capacity < W::zero() {
+                return Err(format!("invalid edge capacity: {:?}", capacity));
+            }
+            if !cost.is_valid() || cost < W::zero() {
+                return Err(format!("invalid edge cost: {:?}", cost));
+            }
+        }
+        let edges = data
+            .edges
+            .into_iter()
+            .zip(data.costs)
+            .map(|((source, target, capacity), cost)| (source, target, capacity, cost))
+            .collect();
+        Ok(MaxFlow::with_costs(data.num_nodes, edges))
+    }
+}
+
+impl<W: Weight> MaxFlow<W> {
+    /// Create a new flow network with edges tuple(current, neighbor, capacity) Vec
+    ///
+    /// # Panics
+    ///
+    /// Panics if an edge refers to a node out of range or if a capacity is negative or NaN.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flex_algo::MaxFlow;
+    ///
+    /// let edges = vec![(0, 1, 10), (0, 2, 5), (1, 2, 15), (1, 3, 5), (2, 3, 10)];
+    /// let network = MaxFlow::new(4, edges);
+    ///
+    /// ```
+    pub fn new(num_nodes: usize, edges: Vec<(usize, usize, W)>) -> Self {
+        let edges = edges.into_iter().map(|(source, target, capacity)| (source, target, capacity, W::zero())).collect();
+        MaxFlow::with_costs(num_nodes, edges)
+    }
+
+    /// Create a new flow network with edges tuple(current, neighbor, capacity, unit cost) Vec
+    ///
+    /// # Panics
+    ///
+    /// Panics if an edge refers to a node out of range or if a capacity or a cost is negative or NaN.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flex_algo::MaxFlow;
+    ///
+    /// let edges = vec![(0, 1, 4, 1), (0, 2, 2, 5), (1, 2, 2, 1), (1, 3, 1, 8), (2, 3, 4, 1)];
+    /// let network = MaxFlow::with_costs(4, edges);
+    ///
+    /// ```
+    pub fn with_costs(num_nodes: usize, edges: Vec<(usize, usize, W, W)>) -> Self {
+        let mut adjacent_list = vec![Vec::new(); num_nodes];
+        let mut costs = Vec::with_capacity(edges.len());
+        let mut capacities = Vec::with_capacity(edges.len());
+        for (id, (source, target, capacity, cost)) in edges.into_iter().enumerate() {
+            assert!(source < num_nodes, "invalid node: {}", source);
+            assert!(target < num_nodes, "invalid node: {}", target);
+            assert!(capacity.is_valid() && capacity >= W::zero(), "invalid edge capacity: {:?}", capacity);
+            assert!(cost.is_valid() && cost >= W::zero(), "invalid edge cost: {:?}", cost);
+            adjacent_list[source].push(2 * id);
+            adjacent_list[target].push(2 * id + 1);
+            capacities.push((source, target, capacity));
+            costs.push(cost);
+        }
+        MaxFlow {
+            edges: capacities,
+            costs,
+            adjacent_list,
+            num_nodes,
+        }
+    }
+
+    // the head of the arc
+    fn _target(&self, arc: usize) -> usize {
+        let (source, target, _) = self.edges[arc / 2];
+        if arc % 2 == 0 {
+            return target;
+        }
+        source
+    }
+
+    // the residual capacity of every arc when there is no flow
+    fn _residuals(&self) -> Vec<W> {
+        self.edges.iter().flat_map(|&(_, _, capacity)| [capacity, W::zero()]).collect()
+    }
+
+    // the breadth first levels of the nodes reachable from the source in the residual graph
+    fn _levels(&self, residual: &[W], source: usize) -> Vec<Option<usize>> {
+        let mut levels = vec![None; self.num_nodes];
+        let mut queue = VecDeque::new();
+        levels[source] = Some(0);
+        queue.push_back(source);
+        while let Some(vertex) = queue.pop_front() {
+            let level = levels[vertex].unwrap();
+            for &arc in &self.adjacent_list[vertex] {
+                let target = self._target(arc);
+                if residual[arc] > W::zero() && levels[target].is_none() {
+                    levels[target] = Some(level + 1);
+                    queue.push_back(target);
+                }
+            }
+        }
+        levels
+    }
+
+    // the arcs of a path from the source to the sink in the level graph,
+    // the dead ends are removed from the level graph and the arcs already tried are skipped
+    fn _blocking_path(&self, residual: &[W], levels: &mut [Option<usize>], next: &mut [usize], source: usize, sink: usize) -> Option<Vec<usize>> {
+        let mut path = Vec::new();
+        let mut vertex = source;
+        while vertex != sink {
+            let level = levels[vertex]?;
+            let arcs = &self.adjacent_list[vertex];
+            let mut advanced = false;
+            while next[vertex] < arcs.len() {
+                let arc = arcs[next[vertex]];
+                let target = self._target(arc);
+                if residual[arc] > W::zero() && levels[target] == Some(level + 1) {
+                    path.push(arc);
+                    vertex = target;
+                    advanced = true;
+                    break;
+                }
+                next[vertex] += 1;
+            }
+            if !advanced {
+                levels[vertex] = None;
+                let arc = path.pop()?;
+                vertex = self._target(arc ^ 1);
+                next[vertex] += 1;
+            }
+        }
+        Some(path)
+    }
+
+    // push the bottleneck of the path, capped by the limit, and return it
+    fn _augment(&self, residual: &mut [W], path: &[usize], limit: Option<W>) -> W {
+        let mut bottleneck = residual[path[0]];
+        for &arc in path {
+            if residual[arc] < bottleneck {
+                bottleneck = residual[arc];
+            }
+        }
+        if let Some(limit) = limit {
+            if limit < bottleneck {
+                bottleneck = limit;
+            }
+        }
+        for &arc in path {
+            residual[arc] = residual[arc].checked_sub(bottleneck).unwrap();
+            residual[arc ^ 1] = residual[arc ^ 1].checked_add(bottleneck).expect("flow overflow");
+        }
+        bottleneck
+    }
+
+    /// Compute a maximum flow from the source to the sink by the Dinic algorithm in O(n^2 m)
+    /// with the flow of every edge and a minimum cut
+    ///
+    /// # Panics
+    ///
+    /// Panics if the source and the sink are the same node, or if the flow overflows.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flex_algo::MaxFlow;
+    ///
+    /// let edges = vec![(0, 1, 10), (0, 2, 5), (1, 2, 15), (1, 3, 5), (2, 3, 10)];
+    /// let network = MaxFlow::new(4, edges);
+    /// let flow = network.max_flow(0, 3);
+    /// assert_eq!(flow.value(), 15);
+    /// assert_eq!(flow.flows(), &[10, 5, 5, 5, 10]);
+    /// // the edges leaving the source are saturated
+    /// assert_eq!(flow.source_side(), vec![0]);
+    /// assert_eq!(flow.cut_edges(), &[0, 1]);
+    ///
+    /// ```
+    pub fn max_flow(&self, source: usize, sink: usize) -> Flow<W> {
+        assert!(source != sink, "the source and the sink must be different nodes");
+        let mut residual = self._residuals();
+        let mut value = W::zero();
+        loop {
+            let mut levels = self._levels(&residual, source);
+            if levels[sink].is_none() {
+                // the nodes reachable from the source are the source side of a minimum cut
+                return self._flow(value, &residual, &levels);
+            }
+            let mut next = vec![0; self.num_nodes];
+            while let Some(path) = self._blocking_path(&residual, &mut levels, &mut next, source, sink) {
+                let bottleneck = self._augment(&mut residual, &path, None);
+                value = value.checked_add(bottleneck).expect("flow overflow");
+            }
+        }
+    }
+
+    /// Compute a minimum cost flow from the source to the sink by successive shortest paths,
+    /// Dijkstra on the costs reduced by potentials, a maximum flow without limit,
+    /// otherwise a flow of the limit value or of the maximum flow value if it is less
+    ///
+    /// # Panics
+    ///
+    /// Panics if the source and the sink are the same node, or if the flow or the cost overflows.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flex_algo::MaxFlow;
+    ///
+    /// let edges = vec![(0, 1, 4, 1), (0, 2, 2, 5), (1, 2, 2, 1), (1, 3, 1, 8), (2, 3, 4, 1)];
+    /// let network = MaxFlow::with_costs(4, edges);
+    /// let flow = network.min_cost_flow(0, 3, None);
+    /// assert_eq!(flow.value(), 5);
+    /// assert_eq!(flow.cost(), 27);
+    ///
+    /// let flow = network.min_cost_flow(0, 3, Some(2));
+    /// assert_eq!(flow.value(), 2);
+    /// assert_eq!(flow.cost(), 6);
+    ///
+    /// ```
+    pub fn min_cost_flow(&self, source: usize, sink: usize, limit: Option<W>) -> Flow<W> {
+        assert!(source != sink, "the source and the sink must be different nodes");
+        let mut residual = self._residuals();
+        // the reduced cost of a residual arc (u, v) is cost + potential(u) - potential(v) >= 0
+        let mut potentials = vec![W::zero(); self.num_nodes];
+        let mut value = W::zero();
+        while limit.is_none_or(|limit| value < limit) {
+            let (distances, predecessors) = self._reduced_dijkstra(&residual, &potentials, source);
+            if distances[sink].is_none() {
+                break;
+            }
+            // the nodes unreachable from the source stay unreachable, their potential is never used again
+            for (potential, distance) in potentials.iter_mut().zip(&distances) {
+                if let Some(distance) = distance {
+                    *potential = potential.checked_add(*distance).expect("cost overflow");
+                }
+            }
+            let mut path = Vec::new();
+            let mut vertex = sink;
+            while vertex != source {
+                let arc = predecessors[vertex].unwrap();
+                path.push(arc);
+                vertex = self._target(arc ^ 1);
+            }
+            let remaining = limit.map(|limit| limit.checked_sub(value).unwrap());
+            let bottleneck = self._augment(&mut residual, &path, remaining);
+            value = value.checked_add(bottleneck).expect("flow overflow");
+        }
+        let levels = self._levels(&residual, source);
+        self._flow(value, &residual, &levels)
+    }
+
+    fn _reduced_cost(&self, potentials: &[W], arc: usize) -> W {
+        let (source, target, _) = self.edges[arc / 2];
+        let cost = self.costs[arc / 2];
+        // the costs and the potentials are not negative, the difference of two potentials always fits
+        // where cost + potential may overflow, only a reduced cost that doesn't fit is an overflow
+        let (tail, head) = if arc % 2 == 0 { (source, target) } else { (target, source) };
+        let reduced = if potentials[tail] >= potentials[head] {
+            let difference = potentials[tail].checked_sub(potentials[head]).unwrap();
+            if arc % 2 == 0 {
+                cost.checked_add(difference)
+            } else {
+                difference.checked_sub(cost)
+            }
+        } else {
+            let difference = potentials[head].checked_sub(potentials[tail]).unwrap();
+            if arc % 2 == 0 {
+                cost.checked_sub(difference)
+            } else {
+                // -cost - difference is never positive
+                Some(W::zero())
+            }
+        };
+        // float rounding may leave a tiny negative reduced cost
+        match reduced {
+            Some(reduced) if reduced < W::zero() => W::zero(),
+            Some(reduced) => reduced,
+            None => panic!("cost overflow"),
+        }
+    }
+
+    fn _reduced_dijkstra(&self, residual: &[W], potentials: &[W], source: usize) -> (Vec<Option<W>>, Vec<Option<usize>>) {
+        let mut distances = vec![None; self.num_nodes];
+        let mut predecessors = vec![None; self.num_nodes];
+        let mut heap = IndexedPriorityQueue::new(|a: &W, b: &W| a < b);
+        distances[source] = Some(W::zero());
+        heap.push(source, W::zero());
+        while let Some((vertex, distance)) = heap.pop() {
+            for &arc in &self.adjacent_list[vertex] {
+                if residual[arc] <= W::zero() {
+                    continue;
+                }
+                let target = self._target(arc);
+                let distance = match distance.checked_add(self._reduced_cost(potentials, arc)) {
+                    Some(distance) => distance,
+                    None => continue,
+                };
+                if distances[target].is_none_or(|current| distance < current) {
+                    distances[target] = Some(distance);
+                    predecessors[target] = Some(arc);
+                    heap.push(target, distance);
+                }
+            }
+        }
+        (distances, predecessors)
+    }
+
+    fn _flow(&self, value: W, residual: &[W], levels: &[Option<usize>]) -> Flow<W> {
+        let mut flows = Vec::with_capacity(self.edges.len());
+        let mut cost = W::zero();
+        let mut cut_edges = Vec::new();
+        for (id, &(source, target, capacity)) in self.edges.iter().enumerate() {
+            let flow = capacity.checked_sub(residual[2 * id]).unwrap();
+            cost = flow
+                .checked_mul(self.costs[id])
+                .and_then(|edge_cost| cost.checked_add(edge_cost))
+                .expect("cost overflow");
+            flows.push(flow);
+            if levels[source].is_some() && levels[target].is_none() {
+                cut_edges.push(id);
+            }
+        }
+        Flow {
+            value,
+            cost,
+            flows,
+            source_side: levels.iter().map(|level| level.is_some()).collect(),
+            cut_edges,
+        }
+    }
+}
+
+/// Flow
+///
+/// The flow value, the flow and the cost of every edge of a flow computed by `MaxFlow`,
+/// and the cut between the nodes reachable from the source in the residual graph and the others,
+/// a minimum cut when the flow is maximum.
+///
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Flow<W = usize> {
+    value: W,
+    cost: W,
+    flows: Vec<W>,
+    source_side: Vec<bool>,
+    cut_edges: Vec<usize>,
+}
+
+impl<W: Weight> Flow<W> {
+    /// Return the value of the flow, the flow leaving the source
+    pub fn value(&self) -> W {
+        self.value
+    }
+
+    /// Return the total cost of the flow, zero without costs
+    pub fn cost(&self) -> W {
+        self.cost
+    }
+
+    /// Return the flow of every edge, in the order of the edges
+    pub fn flows(&self) -> &[W] {
+        &self.flows
+    }
+
+    /// Return the flow of the edge
+    pub fn flow(&self, edge: usize) -> Option<W> {
+        self.flows.get(edge).cloned()
+    }
+
+    /// Return true if the node is on the source side of the cut
+    pub fn is_source_side(&self, node: usize) -> bool {
+        self.source_side.get(node).is_some_and(|&side| side)
+    }
+
+    /// Return the nodes on the source side of the cut
+    pub fn source_side(&self) -> Vec<usize> {
+        (0..self.source_side.len()).filter(|&node| self.source_side[node]).collect()
+    }
+
+    /// Return the nodes on the sink side of the cut
+    pub fn sink_side(&self) -> Vec<usize> {
+        (0..self.source_side.len()).filter(|&node| !self.source_side[node]).collect()
+    }
+
+    /// Return the ids of the edges from the source side to the sink side of the cut
+    pub fn cut_edges(&self) -> &[usize] {
+        &self.cut_edges
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn edges() -> Vec<(usize, usize, u32)> {
+        vec![
+            (0, 1, 16), (0, 2, 13), (1, 2, 10), (2, 1, 4), (1, 3, 12),
+            (3, 2, 9), (2, 4, 14), (4, 3, 7), (3, 5, 20), (4, 5, 4)
+        ]
+    }
+
+    #[test]
+    fn test_max_flow() {
+        let network = MaxFlow::new(6, edges());
+        println!("network: {:?}", network);
+        let flow = network.max_flow(0, 5);
+        println!("flow: {:?}", flow);
+        assert_eq!(flow.value(), 23);
+        assert_eq!(flow.cost(), 0);
+        // the flow is conserved at every inner node and never exceeds the capacities
+        let mut balance = [0i64; 6];
+        for (&(source, target, capacity), &edge_flow) in edges().iter().zip(flow.flows()) {
+            assert!(edge_flow <= capacity);
+            balance[source] -= edge_flow as i64;
+            balance[target] += edge_flow as i64;
+        }
+        assert_eq!(balance, [-23, 0, 0, 0, 0, 23]);
+        // the capacity of the minimum cut is the flow value
+        let cut: u32 = flow.cut_edges().iter().map(|&edge| edges()[edge].2).sum();
+        assert_eq!(cut, 23);
+        assert_eq!(flow.source_side(), vec![0, 1, 2, 4]);
+        assert_eq!(flow.sink_side(), vec![3, 5]);
+        assert!(flow.is_source_side(4));
+        assert_eq!(flow.flow(10), None);
+    }
+
+    #[test]
+    fn test_max_flow_disconnected() {
+        let network = MaxFlow::new(4, vec![(0, 1, 2.5), (2, 3, 1.0)]);
+        let flow = network.max_flow(0, 3);
+        assert_eq!(flow.value(), 0.0);
+        assert_eq!(flow.source_side(), vec![0, 1]);
+        assert!(flow.cut_edges().is_empty());
+    }
+
+    #[test]
+    fn test_max_flow_parallel_edges() {
+        let network = MaxFlow::new(3, vec![(0, 1, 1.5), (0, 1, 2.0), (1, 2, 5.0), (2, 0, 4.0)]);
+        let flow = network.max_flow(0, 2);
+        assert_eq!(flow.value(), 3.5);
+        assert_eq!(flow.flows(), &[1.5, 2.0, 3.5, 0.0]);
+    }
+
+    #[test]
+    fn test_min_cost_flow() {
+        // two routes from 0 to 3, the cheap one has a small capacity
+        let edges = vec![(0, 1, 3, 1), (1, 3, 3, 1), (0, 2, 5, 4), (2, 3, 5, 4), (1, 2, 2, 0)];
+        let network = MaxFlow::with_costs(4, edges);
+        let flow = network.min_cost_flow(0, 3, None);
+        assert_eq!(flow.value(), 8);
+        assert_eq!(flow.cost(), 3 * 2 + 5 * 8);
+        assert_eq!(flow.value(), network.max_flow(0, 3).value());
+
+        let flow = network.min_cost_flow(0, 3, Some(4));
+        assert_eq!(flow.value(), 4);
+        assert_eq!(flow.cost(), 3 * 2 + 8);
+        assert_eq!(flow.flows(), &[3, 3, 1, 1, 0]);
+    }
+
+    #[test]
+    fn test_min_cost_flow_reverse_arc() {
+        // the second path cancels the flow of the middle edge taken by the first one
+        let edges = vec![(0, 1, 1, 1), (0, 2, 1, 2), (1, 2, 1, 0), (1, 3, 1, 3), (2, 3, 1, 1)];
+        let network = MaxFlow::with_costs(4, edges);
+        let flow = network.min_cost_flow(0, 3, None);
+        assert_eq!(flow.value(), 2);
+        assert_eq!(flow.cost(), 7);
+        assert_eq!(flow.flows(), &[1, 1, 0, 1, 1]);
+    }
+
+    #[test]
+    fn test_min_cost_flow_narrow_costs() {
+        // after the first path the potentials of 1 and 2 are 100, the edge 1 -> 2 has a reduced cost of
+        // 200 + 100 - 100 while 200 + 100 overflows an u8
+        let edges: Vec<(usize, usize, u8, u8)> = vec![(0, 1, 1, 100), (0, 2, 1, 100), (1, 2, 1, 200), (2, 3, 1, 0), (1, 3, 1, 27)];
+        let flow = MaxFlow::with_costs(4, edges).min_cost_flow(0, 3, None);
+        assert_eq!(flow.value(), 2);
+        assert_eq!(flow.cost(), 227);
+        assert_eq!(flow.flows(), &[1, 1, 0, 1, 1]);
+    }
+
+    #[test]
+    #[should_panic(expected = "cost overflow")]
+    fn test_min_cost_flow_cost_overflow() {
+        // the second unit of flow costs 100 + 50 + 0, more than an i8
+        let edges: Vec<(usize, usize, i8, i8)> = vec![(0, 1, 1, 100), (0, 2, 1, 10), (1, 2, 1, 50), (2, 3, 2, 0)];
+        MaxFlow::with_costs(4, edges).min_cost_flow(0, 3, None);
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid edge capacity")]
+    fn test_max_flow_negative_capacity() {
+        MaxFlow::new(2, vec![(0, 1, -1)]);
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid node: 3")]
+    fn test_max_flow_invalid_node() {
+        MaxFlow::with_costs(3, vec![(0, 1, 2, 1), (1, 3, 2, 1)]);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_max_flow_serde() {
+        let network = MaxFlow::with_costs(3, vec![(0, 1, 2, 1), (1, 2, 3, 4)]);
+        let json = serde_json::to_string(&network).unwrap();
+        assert_eq!(json, r#"{"edges":[[0,1,2],[1,2,3]],"costs":[1,4],"num_nodes":3}"#);
+        let network: MaxFlow<i32> = serde_json::from_str(&json).unwrap();
+        assert_eq!(network.min_cost_flow(0, 2, None).cost(), 10);
+
+        // the edges are checked as by with_costs
+        let json = r#"{"edges":[[0,1,2],[1,3,3]],"costs":[1,4],"num_nodes":3}"#;
+        let error = serde_json::from_str::<MaxFlow<i32>>(json).unwrap_err();
+        assert!(error.to_string().starts_with("invalid node: 3"));
+        let json = r#"{"edges":[[0,1,2],[1,2,3]],"costs":[1],"num_nodes":3}"#;
+        let error = serde_json::from_str::<MaxFlow<i32>>(json).unwrap_err();
+        assert!(error.to_string().starts_with("invalid number of costs: 1"));
+        let json = r#"{"edges":[[0,1,-2]],"costs":[1],"num_nodes":2}"#;
+        let error = serde_json::from_str::<MaxFlow<i32>>(json).unwrap_err();
+        assert!(error.to_string().starts_with("invalid edge capacity: -2"));
+        let json = r#"{"edges":[[0,1,2]],"costs":[1],"adjacent_list":[[7],[]],"num_nodes":2}"#;
+        let network: MaxFlow<i32> = serde_json::from_str(json).unwrap();
+        assert_eq!(network.max_flow(0, 1).value(), 2);
+    }
+}
@@ -56,6 +56,25 @@ where
         }
     }
 
+    /// Create a new PriorityQueue with a comparator function from the elements of a Vec,
+    /// e.g. the heap contents of a deserialized PriorityQueue
+    /// 
+    /// # Example
+    /// 
+    /// ```
+    /// use flex_algo::priority_queue::PriorityQueue;
+    /// 
+    /// let mut pq = PriorityQueue::from_vec(|a: &usize,b: &usize| a < b, vec![14, 10, 12]);
+    /// assert_eq!(pq.pop().unwrap(), 10);
+    /// ```
+    pub fn from_vec(comparator: F, elements: Vec<T>) -> Self {
+        let mut pq = PriorityQueue::new(comparator);
+        for element in elements {
+            pq.push(element);
+        }
+        pq
+    }
+
     /// Return the size of the PriorityQueue
     /// 
     /// # Example
@@ -192,7 +211,21 @@ where
     }
 }
 
+// only the heap contents are serialized, the comparator has to be provided again by
+// `PriorityQueue::from_vec` after deserializing them as a Vec
+#[cfg(feature = "serde")]
+impl<F, T> serde::Serialize for PriorityQueue<F, T>
+where
+    F: Fn(&T, &T) -> bool,
+    T: PartialOrd + Debug + serde::Serialize,
+{
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.heap.serialize(serializer)
+    }
+}
+
 #[cfg(test)]
+#[allow(clippy::bool_assert_comparison, clippy::get_first)]
 mod tests {
     use super::*;
 
@@ -240,4 +273,17 @@ mod tests {
         assert_eq!(value, 0);
         // panic!();
     }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_priority_queue_serde() {
+        let pq = PriorityQueue::from_vec(compare, vec![14, 12, 5, 7, 8, 3]);
+        let json = serde_json::to_string(&pq).unwrap();
+        let heap: Vec<usize> = serde_json::from_str(&json).unwrap();
+        assert_eq!(heap, pq.heap);
+
+        let mut pq = PriorityQueue::from_vec(compare, heap);
+        assert_eq!(pq.pop().unwrap(), 14);
+        assert_eq!(pq.pop().unwrap(), 12);
+    }
 }
@@ -0,0 +1,221 @@
+use std::fmt::Debug;
+use crate::disjoint_set::DisjointSet;
+use crate::priority_queue::PriorityQueue;
+use crate::weight::Weight;
+
+/// Minimum spanning forest
+///
+/// This crate implements the Kruskal and Prim algorithms to compute a minimum spanning forest
+/// of an undirected weighted graph, a minimum spanning tree of every connected component.
+///
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SpanningForest<W = usize> {
+    edges: Vec<(usize, usize, W)>,
+    total_weight: W,
+    tree_count: usize,
+}
+
+impl<W: Weight> SpanningForest<W> {
+    /// Compute a minimum spanning forest by the Kruskal algorithm in O(m log m)
+    /// with undirected edges tuple(node, node, weight) Vec
+    ///
+    /// # Panics
+    ///
+    /// Panics if a weight is NaN or if the total weight overflows.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flex_algo::SpanningForest;
+    ///
+    /// let edges = vec![(0, 1, 4), (1, 2, 2), (0, 2, 5), (2, 3, 1), (1, 3, 3)];
+    /// let forest = SpanningForest::kruskal(4, edges);
+    /// assert_eq!(forest.total_weight(), 7);
+    /// assert_eq!(forest.edges(), &[(2, 3, 1), (1, 2, 2), (0, 1, 4)]);
+    /// assert!(forest.is_tree());
+    ///
+    /// ```
+    pub fn kruskal(num_nodes: usize, edges: Vec<(usize, usize, W)>) -> Self {
+        let mut edges = edges;
+        for &(_, _, weight) in &edges {
+            assert!(weight.is_valid(), "invalid edge weight: {:?}", weight);
+        }
+        edges.sort_by(|a, b| a.2.partial_cmp(&b.2).unwrap());
+
+        let mut set = DisjointSet::new(num_nodes);
+        let mut chosen = Vec::new();
+        for (a, b, weight) in edges {
+            // an edge inside a tree would close a cycle
+            if set.union(a, b) {
+                chosen.push((a, b, weight));
+            }
+        }
+        SpanningForest::new(num_nodes, chosen)
+    }
+
+    /// Compute a minimum spanning forest by the Prim algorithm in O(m log m)
+    /// with undirected edges tuple(node, node, weight) Vec, every tree grows from its smallest node
+    ///
+    /// # Panics
+    ///
+    /// Panics if a weight is NaN or if the total weight overflows.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flex_algo::SpanningForest;
+    ///
+    /// let edges = vec![(0, 1, 4), (1, 2, 2), (0, 2, 5), (2, 3, 1), (1, 3, 3)];
+    /// let forest = SpanningForest::prim(4, edges);
+    /// assert_eq!(forest.total_weight(), 7);
+    /// assert_eq!(forest.edges(), &[(0, 1, 4), (1, 2, 2), (2, 3, 1)]);
+    ///
+    /// ```
+    pub fn prim(num_nodes: usize, edges: Vec<(usize, usize, W)>) -> Self {
+        let mut adjacent_list = vec![Vec::new(); num_nodes];
+        for &(a, b, weight) in &edges {
+            assert!(weight.is_valid(), "invalid edge weight: {:?}", weight);
+            adjacent_list[a].push((b, weight));
+            adjacent_list[b].push((a, weight));
+        }
+
+        let mut in_tree = vec![false; num_nodes];
+        let mut chosen = Vec::new();
+        // the heap holds (weight, node in the tree, node out of it), stale entries are skipped when popped
+        let mut heap = PriorityQueue::new(|a: &(W, usize, usize), b: &(W, usize, usize)| a.0 < b.0);
+        for root in 0..num_nodes {
+            if in_tree[root] {
+                continue;
+            }
+            in_tree[root] = true;
+            for &(neighbor, weight) in &adjacent_list[root] {
+                heap.push((weight, root, neighbor));
+            }
+            while let Some((weight, vertex, neighbor)) = heap.pop() {
+                if in_tree[neighbor] {
+                    continue;
+                }
+                in_tree[neighbor] = true;
+                chosen.push((vertex, neighbor, weight));
+                for &(next, weight) in &adjacent_list[neighbor] {
+                    if !in_tree[next] {
+                        heap.push((weight, neighbor, next));
+                    }
+                }
+            }
+        }
+        SpanningForest::new(num_nodes, chosen)
+    }
+
+    /// Compute a minimum spanning tree by the Kruskal algorithm,
+    /// or `None` if the graph is not connected
+    ///
+    /// # Panics
+    ///
+    /// Panics if a weight is NaN or if the total weight overflows, as `kruskal`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flex_algo::SpanningForest;
+    ///
+    /// assert!(SpanningForest::minimum_spanning_tree(3, vec![(0, 1, 1.5), (1, 2, 0.5)]).is_some());
+    /// assert!(SpanningForest::minimum_spanning_tree(3, vec![(0, 1, 1.5)]).is_none());
+    ///
+    /// ```
+    pub fn minimum_spanning_tree(num_nodes: usize, edges: Vec<(usize, usize, W)>) -> Option<Self> {
+        let forest = SpanningForest::kruskal(num_nodes, edges);
+        if !forest.is_tree() {
+            return None;
+        }
+        Some(forest)
+    }
+
+    // the forest of the chosen edges, panics if their total weight overflows
+    fn new(num_nodes: usize, edges: Vec<(usize, usize, W)>) -> Self {
+        let mut total_weight = W::zero();
+        for &(_, _, weight) in &edges {
+            total_weight = total_weight.checked_add(weight).expect("total weight overflow");
+        }
+        // every edge of a forest merges two trees
+        let tree_count = num_nodes - edges.len();
+        SpanningForest {
+            edges,
+            total_weight,
+            tree_count,
+        }
+    }
+
+    /// Return the edges of the forest in the order they were chosen
+    pub fn edges(&self) -> &[(usize, usize, W)] {
+        &self.edges
+    }
+
+    /// Return the total weight of the edges
+    pub fn total_weight(&self) -> W {
+        self.total_weight
+    }
+
+    /// Return the number of trees, the number of connected components of the graph
+    pub fn tree_count(&self) -> usize {
+        self.tree_count
+    }
+
+    /// Return true if the forest is a single tree spanning the whole graph
+    pub fn is_tree(&self) -> bool {
+        self.tree_count <= 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn edges() -> Vec<(usize, usize, u32)> {
+        vec![
+            (0, 1, 4), (0, 7, 8), (1, 2, 8), (1, 7, 11), (2, 3, 7), (2, 8, 2), (2, 5, 4),
+            (3, 4, 9), (3, 5, 14), (4, 5, 10), (5, 6, 2), (6, 7, 1), (6, 8, 6), (7, 8, 7)
+        ]
+    }
+
+    #[test]
+    fn test_kruskal() {
+        let forest = SpanningForest::kruskal(9, edges());
+        println!("kruskal: {:?}", forest);
+        assert_eq!(forest.total_weight(), 37);
+        assert_eq!(forest.edges().len(), 8);
+        assert_eq!(forest.tree_count(), 1);
+        assert!(forest.is_tree());
+    }
+
+    #[test]
+    fn test_prim() {
+        let forest = SpanningForest::prim(9, edges());
+        println!("prim: {:?}", forest);
+        assert_eq!(forest.total_weight(), 37);
+        assert_eq!(forest.edges().len(), 8);
+        assert!(forest.is_tree());
+    }
+
+    #[test]
+    fn test_spanning_forest() {
+        // two components and an isolated node, with a self loop and a parallel edge
+        let edges = vec![(0, 1, 2.5), (1, 2, 1.0), (0, 2, 0.5), (3, 4, 3.0), (4, 3, 1.5), (4, 4, 0.0)];
+        let kruskal = SpanningForest::kruskal(6, edges.clone());
+        let prim = SpanningForest::prim(6, edges.clone());
+        assert_eq!(kruskal.total_weight(), 3.0);
+        assert_eq!(prim.total_weight(), 3.0);
+        assert_eq!(kruskal.tree_count(), 3);
+        assert_eq!(prim.tree_count(), 3);
+        assert!(!kruskal.is_tree());
+        assert_eq!(SpanningForest::minimum_spanning_tree(6, edges), None);
+        assert_eq!(SpanningForest::<i32>::kruskal(0, vec![]).tree_count(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "total weight overflow")]
+    fn test_spanning_forest_overflow() {
+        SpanningForest::kruskal(3, vec![(0, 1, 200u8), (1, 2, 100)]);
+    }
+}
@@ -0,0 +1,462 @@
+use std::collections::VecDeque;
+use std::fmt::Debug;
+use crate::graph::{Edge, Graph};
+
+/// Undirected graph
+///
+/// This crate implements an undirected graph on top of `Graph`, every edge is stored once
+/// and connects its two nodes both ways, with connected components, a bipartite 2-colouring,
+/// the bridges and the articulation points.
+///
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UndirectedGraph<N = (), E = ()> {
+    graph: Graph<N, E>,
+}
+
+impl UndirectedGraph {
+    /// Create a new undirected graph with the given edges tuple(node, node)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flex_algo::UndirectedGraph;
+    ///
+    /// let graph = UndirectedGraph::new(4, vec![(0, 1), (1, 2), (3, 2)]);
+    /// assert_eq!(graph.neighbors(2).collect::<Vec<_>>(), vec![1, 3]);
+    /// ```
+    pub fn new(num_nodes: usize, edges: Vec<(usize, usize)>) -> Self {
+        let edges = edges.into_iter().map(|(a, b)| (a, b, ())).collect();
+        UndirectedGraph::from_edges(vec![(); num_nodes], edges)
+    }
+}
+
+impl<N, E> Default for UndirectedGraph<N, E> {
+    fn default() -> Self {
+        UndirectedGraph {
+            graph: Graph::default(),
+        }
+    }
+}
+
+impl<N, E> From<Graph<N, E>> for UndirectedGraph<N, E> {
+    /// Create a new undirected graph from a directed graph, the direction of the edges is ignored
+    fn from(graph: Graph<N, E>) -> Self {
+        UndirectedGraph { graph }
+    }
+}
+
+impl<N, E> UndirectedGraph<N, E> {
+    /// Create a new undirected graph with the node payloads and the edges tuple(node, node, payload) Vec
+    ///
+    /// # Panics
+    ///
+    /// Panics if an edge refers to a node out of range.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flex_algo::UndirectedGraph;
+    ///
+    /// let graph = UndirectedGraph::from_edges(vec!["a", "b"], vec![(0, 1, 10)]);
+    /// assert!(graph.contains_edge(1, 0));
+    /// ```
+    pub fn from_edges(nodes: Vec<N>, edges: Vec<(usize, usize, E)>) -> Self {
+        UndirectedGraph {
+            graph: Graph::from_edges(nodes, edges),
+        }
+    }
+
+    /// Return the underlying graph, every edge directed from the first to the second node it was added with
+    pub fn graph(&self) -> &Graph<N, E> {
+        &self.graph
+    }
+
+    /// Add a node with its payload and return its id
+    pub fn add_node(&mut self, node: N) -> usize {
+        self.graph.add_node(node)
+    }
+
+    /// Add an edge between the two nodes with its payload and return its id
+    ///
+    /// # Panics
+    ///
+    /// Panics if a node doesn't exist.
+    pub fn add_edge(&mut self, a: usize, b: usize, weight: E) -> usize {
+        self.graph.add_edge(a, b, weight)
+    }
+
+    /// Remove the edge and return its payload, or `None` if it doesn't exist
+    pub fn remove_edge(&mut self, edge: usize) -> Option<E> {
+        self.graph.remove_edge(edge)
+    }
+
+    /// Remove the node with all its edges and return its payload, or `None` if it doesn't exist
+    pub fn remove_node(&mut self, node: usize) -> Option<N> {
+        self.graph.remove_node(node)
+    }
+
+    /// Return the number of nodes
+    pub fn node_count(&self) -> usize {
+        self.graph.node_count()
+    }
+
+    /// Return the number of edges
+    pub fn edge_count(&self) -> usize {
+        self.graph.edge_count()
+    }
+
+    /// Return the payload of the node
+    pub fn node(&self, node: usize) -> Option<&N> {
+        self.graph.node(node)
+    }
+
+    /// Return the edge
+    pub fn edge(&self, edge: usize) -> Option<&Edge<E>> {
+        self.graph.edge(edge)
+    }
+
+    /// Return true if there is an edge between the two nodes
+    pub fn contains_edge(&self, a: usize, b: usize) -> bool {
+        self.graph.contains_edge(a, b) || self.graph.contains_edge(b, a)
+    }
+
+    /// Return the nodes connected to the node, once per edge, a self loop gives the node twice
+    pub fn neighbors(&self, node: usize) -> impl Iterator<Item = usize> + '_ {
+        self.incident_edges(node).map(|(_, neighbor)| neighbor)
+    }
+
+    // the ids of the edges of the node and the node at their other end
+    fn incident_edges(&self, node: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        let outgoing = self.graph.out_edge_ids(node).iter().map(move |&id| (id, self.graph.edge(id).unwrap().target()));
+        let incoming = self.graph.in_edge_ids(node).iter().map(move |&id| (id, self.graph.edge(id).unwrap().source()));
+        outgoing.chain(incoming)
+    }
+
+    /// Return the number of edges of the node, a self loop counts twice
+    pub fn degree(&self, node: usize) -> usize {
+        self.graph.degree(node)
+    }
+
+    /// Return the connected components, numbered in the order of their smallest node
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flex_algo::UndirectedGraph;
+    ///
+    /// let graph = UndirectedGraph::new(6, vec![(0, 1), (1, 2), (3, 4)]);
+    /// let components = graph.connected_components();
+    /// assert_eq!(components.count(), 3);
+    /// assert_eq!(components.components(), &[vec![0, 1, 2], vec![3, 4], vec![5]]);
+    /// assert!(components.is_connected(0, 2));
+    /// assert!(!components.is_connected(2, 3));
+    /// ```
+    pub fn connected_components(&self) -> ConnectedComponents {
+        let mut component_of = vec![None; self.graph.node_bound()];
+        let mut components = Vec::new();
+        for root in self.graph.node_indices() {
+            if component_of[root].is_some() {
+                continue;
+            }
+            let id = components.len();
+            let mut component = vec![root];
+            let mut stack = vec![root];
+            component_of[root] = Some(id);
+            while let Some(vertex) = stack.pop() {
+                for neighbor in self.neighbors(vertex) {
+                    if component_of[neighbor].is_none() {
+                        component_of[neighbor] = Some(id);
+                        component.push(neighbor);
+                        stack.push(neighbor);
+                    }
+                }
+            }
+            component.sort_unstable();
+            components.push(component);
+        }
+        ConnectedComponents {
+            component_of,
+            components,
+        }
+    }
+
+    /// Return true if the nodes can be split in two sides with every edge between the sides
+    pub fn is_bipartite(&self) -> bool {
+        self.two_coloring().is_some()
+    }
+
+    /// Return a colour 0 or 1 for every node so that every edge connects two colours,
+    /// or `None` if the graph is not bipartite, the removed nodes have no colour
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flex_algo::UndirectedGraph;
+    ///
+    /// let square = UndirectedGraph::new(4, vec![(0, 1), (1, 2), (2, 3), (3, 0)]);
+    /// assert_eq!(square.two_coloring(), Some(vec![Some(0), Some(1), Some(0), Some(1)]));
+    ///
+    /// let triangle = UndirectedGraph::new(3, vec![(0, 1), (1, 2), (2, 0)]);
+    /// assert_eq!(triangle.two_coloring(), None);
+    /// ```
+    pub fn two_coloring(&self) -> Option<Vec<Option<usize>>> {
+        let mut colors = vec![None; self.graph.node_bound()];
+        let mut queue = VecDeque::new();
+        for root in self.graph.node_indices() {
+            if colors[root].is_some() {
+                continue;
+            }
+            colors[root] = Some(0);
+            queue.push_back(root);
+            while let Some(vertex) = queue.pop_front() {
+                let color = colors[vertex].unwrap();
+                for neighbor in self.neighbors(vertex) {
+                    match colors[neighbor] {
+                        None => {
+                            colors[neighbor] = Some(1 - color);
+                            queue.push_back(neighbor);
+                        }
+                        Some(neighbor_color) if neighbor_color == color => return None,
+                        _ => {}
+                    }
+                }
+            }
+        }
+        Some(colors)
+    }
+
+    /// Return the ids of the bridges in increasing order,
+    /// the edges whose removal disconnects their two nodes
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flex_algo::UndirectedGraph;
+    ///
+    /// // the triangle 0, 1, 2 is linked to 3 by the edge 3
+    /// let graph = UndirectedGraph::new(5, vec![(0, 1), (1, 2), (2, 0), (2, 3), (3, 4), (4, 3)]);
+    /// assert_eq!(graph.bridges(), vec![3]);
+    /// let edge = graph.edge(3).unwrap();
+    /// assert_eq!((edge.source(), edge.target()), (2, 3));
+    /// ```
+    pub fn bridges(&self) -> Vec<usize> {
+        self._lowlinks().0
+    }
+
+    /// Return the articulation points in increasing order,
+    /// the nodes whose removal disconnects the graph they belong to
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flex_algo::UndirectedGraph;
+    ///
+    /// let graph = UndirectedGraph::new(5, vec![(0, 1), (1, 2), (2, 0), (2, 3), (3, 4)]);
+    /// assert_eq!(graph.articulation_points(), vec![2, 3]);
+    /// ```
+    pub fn articulation_points(&self) -> Vec<usize> {
+        self._lowlinks().1
+    }
+
+    // the bridges and the articulation points by the lowlinks of an iterative depth first search,
+    // the edge to the parent is skipped by id so that parallel edges are never bridges
+    fn _lowlinks(&self) -> (Vec<usize>, Vec<usize>) {
+        let incident: Vec<Vec<(usize, usize)>> = (0..self.graph.node_bound())
+            .map(|node| {
+                if self.graph.contains_node(node) {
+                    return self.incident_edges(node).collect();
+                }
+                Vec::new()
+            })
+            .collect();
+        let mut discovery = vec![None; self.graph.node_bound()];
+        let mut lowlink = vec![0; self.graph.node_bound()];
+        let mut is_articulation = vec![false; self.graph.node_bound()];
+        let mut bridges = Vec::new();
+        let mut time = 0;
+
+        for root in self.graph.node_indices() {
+            if discovery[root].is_some() {
+                continue;
+            }
+            discovery[root] = Some(time);
+            lowlink[root] = time;
+            time += 1;
+            let mut root_children = 0;
+            // every frame holds a vertex, the edge it was reached by and the index of its next edge
+            let mut frames: Vec<(usize, Option<usize>, usize)> = vec![(root, None, 0)];
+
+            while let Some(frame) = frames.last_mut() {
+                let (vertex, parent_edge) = (frame.0, frame.1);
+                if let Some(&(edge, neighbor)) = incident[vertex].get(frame.2) {
+                    frame.2 += 1;
+                    if Some(edge) == parent_edge {
+                        continue;
+                    }
+                    match discovery[neighbor] {
+                        None => {
+                            discovery[neighbor] = Some(time);
+                            lowlink[neighbor] = time;
+                            time += 1;
+                            if vertex == root {
+                                root_children += 1;
+                            }
+                            frames.push((neighbor, Some(edge), 0));
+                        }
+                        Some(neighbor_discovery) => {
+                            lowlink[vertex] = lowlink[vertex].min(neighbor_discovery);
+                        }
+                    }
+                    continue;
+                }
+                frames.pop();
+                if let Some(&(parent, _, _)) = frames.last() {
+                    lowlink[parent] = lowlink[parent].min(lowlink[vertex]);
+                    let parent_discovery = discovery[parent].unwrap();
+                    if lowlink[vertex] > parent_discovery {
+                        bridges.push(parent_edge.unwrap());
+                    }
+                    if parent != root && lowlink[vertex] >= parent_discovery {
+                        is_articulation[parent] = true;
+                    }
+                }
+            }
+            if root_children > 1 {
+                is_articulation[root] = true;
+            }
+        }
+        bridges.sort_unstable();
+        let articulation_points = (0..self.graph.node_bound()).filter(|&node| is_articulation[node]).collect();
+        (bridges, articulation_points)
+    }
+}
+
+/// Connected components
+///
+/// The connected components of an undirected graph, the component id of every node
+/// and the sorted nodes of every component, numbered in the order of their smallest node.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ConnectedComponents {
+    component_of: Vec<Option<usize>>,
+    components: Vec<Vec<usize>>,
+}
+
+impl ConnectedComponents {
+    /// Return the number of components
+    pub fn count(&self) -> usize {
+        self.components.len()
+    }
+
+    /// Return the component id of the node
+    pub fn component_of(&self, node: usize) -> Option<usize> {
+        self.component_of.get(node).cloned().flatten()
+    }
+
+    /// Return the nodes of every component
+    pub fn components(&self) -> &[Vec<usize>] {
+        &self.components
+    }
+
+    /// Return true if the two nodes are in the same component
+    pub fn is_connected(&self, a: usize, b: usize) -> bool {
+        self.component_of(a).is_some_and(|component| self.component_of(b) == Some(component))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_undirected_graph() {
+        let mut graph = UndirectedGraph::from_edges(vec!["a", "b", "c"], vec![(0, 1, 1), (2, 1, 2), (2, 2, 3)]);
+        println!("undirected graph: {:?}", graph);
+        assert_eq!(graph.neighbors(1).collect::<Vec<_>>(), vec![0, 2]);
+        assert_eq!(graph.neighbors(2).collect::<Vec<_>>(), vec![1, 2, 2]);
+        assert_eq!(graph.degree(2), 3);
+        assert!(graph.contains_edge(1, 2));
+        assert!(!graph.contains_edge(0, 2));
+        assert_eq!(graph.remove_node(1), Some("b"));
+        assert_eq!(graph.edge_count(), 1);
+        let d = graph.add_node("d");
+        graph.add_edge(d, 0, 4);
+        assert_eq!(graph.neighbors(0).collect::<Vec<_>>(), vec![3]);
+        assert_eq!(graph.node_count(), 3);
+    }
+
+    #[test]
+    fn test_connected_components() {
+        let mut graph = UndirectedGraph::new(7, vec![(4, 0), (1, 5), (5, 3), (6, 6)]);
+        let components = graph.connected_components();
+        println!("components: {:?}", components);
+        assert_eq!(components.components(), &[vec![0, 4], vec![1, 3, 5], vec![2], vec![6]]);
+        assert_eq!(components.component_of(3), Some(1));
+        assert!(components.is_connected(1, 3));
+        assert!(!components.is_connected(0, 7));
+
+        graph.remove_node(5);
+        let components = graph.connected_components();
+        assert_eq!(components.count(), 5);
+        assert_eq!(components.component_of(5), None);
+    }
+
+    #[test]
+    fn test_two_coloring() {
+        let graph = UndirectedGraph::new(6, vec![(0, 3), (0, 4), (1, 4), (2, 5)]);
+        let colors = graph.two_coloring().unwrap();
+        assert_eq!(colors, vec![Some(0), Some(0), Some(0), Some(1), Some(1), Some(1)]);
+        assert!(graph.is_bipartite());
+
+        let graph = UndirectedGraph::new(5, vec![(0, 1), (1, 2), (2, 3), (3, 4), (4, 0)]);
+        assert!(!graph.is_bipartite());
+        let graph = UndirectedGraph::new(1, vec![(0, 0)]);
+        assert!(!graph.is_bipartite());
+    }
+
+    #[test]
+    fn test_bridges_and_articulation_points() {
+        let edges = vec![(0, 1), (1, 2), (2, 0), (1, 3), (3, 4), (4, 5), (5, 3), (5, 6), (7, 8), (8, 7)];
+        let graph = UndirectedGraph::new(10, edges);
+        assert_eq!(graph.bridges(), vec![3, 7]);
+        assert_eq!(graph.articulation_points(), vec![1, 3, 5]);
+
+        // a path is all bridges
+        let graph = UndirectedGraph::new(4, vec![(0, 1), (1, 2), (2, 3)]);
+        assert_eq!(graph.bridges(), vec![0, 1, 2]);
+        assert_eq!(graph.articulation_points(), vec![1, 2]);
+
+        // a star, the center is the only articulation point
+        let graph = UndirectedGraph::new(4, vec![(0, 1), (0, 2), (0, 3)]);
+        assert_eq!(graph.articulation_points(), vec![0]);
+    }
+
+    #[test]
+    fn test_bridges_deep() {
+        let num_nodes = 200_000;
+        let edges = (1..num_nodes).map(|node| (node - 1, node)).collect();
+        let graph = UndirectedGraph::new(num_nodes, edges);
+        assert_eq!(graph.bridges().len(), num_nodes - 1);
+        assert_eq!(graph.articulation_points().len(), num_nodes - 2);
+    }
+
+    #[test]
+    fn test_undirected_from_graph() {
+        let graph = Graph::new(3, vec![(1, 0), (1, 2)]);
+        assert!(graph.topological_sort().is_ok());
+        let graph = UndirectedGraph::from(graph);
+        assert_eq!(graph.connected_components().count(), 1);
+        assert_eq!(graph.articulation_points(), vec![1]);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_undirected_graph_serde() {
+        let graph = UndirectedGraph::new(3, vec![(0, 1), (1, 2)]);
+        let json = serde_json::to_string(&graph).unwrap();
+        let graph: UndirectedGraph = serde_json::from_str(&json).unwrap();
+        assert_eq!(graph.bridges(), vec![0, 1]);
+    }
+}
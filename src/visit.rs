@@ -0,0 +1,539 @@
+//! Visit
+//!
+//! This module describes a graph by the traits [GraphBase], [NodeCount], [IntoNeighbors] and [IntoEdges],
+//! and implements the breadth first search, the depth first search, the cycle detection, the topological sort and the Dijkstra algorithm
+//! once over them, so they run unchanged on the adjacency list [Graph](crate::Graph), on the compressed sparse row
+//! [CsrGraph](crate::CsrGraph) and on any other graph storage, e.g. an implicit grid whose edges are never stored.
+//!
+//! The nodes of a graph are the ids below `node_bound`, the `Into` traits take the graph by value
+//! and are implemented for a reference to the graph or for a `Copy` handle.
+//!
+//! # Example
+//!
+//! ```
+//! use flex_algo::visit::{self, GraphBase, IntoEdges, IntoNeighbors, NodeCount};
+//!
+//! // a width x height grid where every cell is connected to the cells beside it
+//! #[derive(Clone, Copy)]
+//! struct Grid {
+//!     width: usize,
+//!     height: usize,
+//! }
+//!
+//! impl GraphBase for Grid {
+//!     fn node_bound(&self) -> usize {
+//!         self.width * self.height
+//!     }
+//! }
+//!
+//! impl NodeCount for Grid {
+//!     fn node_count(&self) -> usize {
+//!         self.width * self.height
+//!     }
+//! }
+//!
+//! impl IntoNeighbors for Grid {
+//!     fn neighbors(self, node: usize) -> impl Iterator<Item = usize> {
+//!         let (x, y) = (node % self.width, node / self.width);
+//!         let left = (x > 0).then(|| node - 1);
+//!         let right = (x + 1 < self.width).then(|| node + 1);
+//!         let up = (y > 0).then(|| node - self.width);
+//!         let down = (y + 1 < self.height).then(|| node + self.width);
+//!         [left, right, up, down].into_iter().flatten()
+//!     }
+//! }
+//!
+//! impl IntoEdges for Grid {
+//!     type EdgeWeight = u32;
+//!
+//!     fn edges(self, node: usize) -> impl Iterator<Item = (usize, u32)> {
+//!         self.neighbors(node).map(|neighbor| (neighbor, 1))
+//!     }
+//! }
+//!
+//! let grid = Grid { width: 4, height: 3 };
+//! assert_eq!(visit::bfs(grid, 0).level(11), Some(5));
+//! assert_eq!(visit::dijkstra(grid, 0).path_to(3), Some((3, vec![0, 1, 2, 3])));
+//! assert_eq!(visit::dfs(grid, 0).preorder().len(), 12);
+//! ```
+//!
+use std::collections::VecDeque;
+use crate::dijkstra::{self, ShortestPaths};
+use crate::graph::{BreadthFirstSearch, Cycle, DepthFirstSearch, EdgeKind};
+use crate::indexed_priority_queue::IndexedPriorityQueue;
+use crate::priority_queue::PriorityQueue;
+use crate::weight::Weight;
+
+/// GraphBase
+///
+/// The nodes of a graph, the ids below `node_bound` that `contains_node`.
+///
+pub trait GraphBase {
+    /// Return the upper bound of the node ids
+    fn node_bound(&self) -> usize;
+
+    /// Return true if the node exists, every id below the bound by default
+    fn contains_node(&self, node: usize) -> bool {
+        node < self.node_bound()
+    }
+}
+
+/// NodeCount
+///
+/// The number of nodes of a graph.
+///
+pub trait NodeCount: GraphBase {
+    /// Return the number of existing nodes
+    fn node_count(&self) -> usize;
+}
+
+/// IntoNeighbors
+///
+/// The outgoing adjacency of a directed graph.
+///
+pub trait IntoNeighbors: GraphBase + Copy {
+    /// Return the targets of the outgoing edges of the node, in the order of the edges
+    fn neighbors(self, node: usize) -> impl Iterator<Item = usize>;
+}
+
+/// IntoEdges
+///
+/// The outgoing edges of a directed graph with their weight.
+///
+pub trait IntoEdges: IntoNeighbors {
+    /// The weight of an edge
+    type EdgeWeight;
+
+    /// Return the targets and the weights of the outgoing edges of the node, in the order of the edges
+    fn edges(self, node: usize) -> impl Iterator<Item = (usize, Self::EdgeWeight)>;
+}
+
+impl<G: GraphBase + ?Sized> GraphBase for &G {
+    fn node_bound(&self) -> usize {
+        (**self).node_bound()
+    }
+
+    fn contains_node(&self, node: usize) -> bool {
+        (**self).contains_node(node)
+    }
+}
+
+impl<G: NodeCount + ?Sized> NodeCount for &G {
+    fn node_count(&self) -> usize {
+        (**self).node_count()
+    }
+}
+
+// the ids of the existing nodes in increasing order
+fn _node_indices<G: GraphBase>(graph: &G) -> impl Iterator<Item = usize> + '_ {
+    (0..graph.node_bound()).filter(move |&node| graph.contains_node(node))
+}
+
+/// Breadth first search from the start node, return the visit order
+/// with the level (hop distance) and the parent of every node
+///
+/// # Panics
+///
+/// Panics if the start node is out of range.
+///
+pub fn bfs<G: IntoNeighbors>(graph: G, start: usize) -> BreadthFirstSearch {
+    let mut queue = VecDeque::new();
+    let mut order = Vec::new();
+    let mut levels = vec![None; graph.node_bound()];
+    let mut parents = vec![None; graph.node_bound()];
+    levels[start] = Some(0);
+    queue.push_back(start);
+
+    while let Some(vertex) = queue.pop_front() {
+        order.push(vertex);
+        let level = levels[vertex].unwrap();
+        for neighbor in graph.neighbors(vertex) {
+            // a node is seen once queued, so it is only queued once
+            if levels[neighbor].is_none() {
+                levels[neighbor] = Some(level + 1);
+                parents[neighbor] = Some(vertex);
+                queue.push_back(neighbor);
+            }
+        }
+    }
+    BreadthFirstSearch {
+        start,
+        order,
+        levels,
+        parents,
+    }
+}
+
+/// Depth first search from the start node, safe on cycles and deep graphs,
+/// return the preorder and postorder, the discovery/finish times and the classified edges
+///
+/// # Panics
+///
+/// Panics if the start node is out of range.
+///
+pub fn dfs<G: IntoNeighbors>(graph: G, start: usize) -> DepthFirstSearch {
+    let mut search = DepthFirstSearch::new(graph.node_bound());
+    let mut time = 0;
+    _dfs_visit(graph, start, &mut search, &mut time);
+    search
+}
+
+/// Depth first search of the whole graph, every undiscovered node in increasing order
+/// starts a new tree of the depth first forest
+pub fn dfs_all<G: IntoNeighbors>(graph: G) -> DepthFirstSearch {
+    let mut search = DepthFirstSearch::new(graph.node_bound());
+    let mut time = 0;
+    for vertex in _node_indices(&graph) {
+        if search.discovery[vertex].is_none() {
+            _dfs_visit(graph, vertex, &mut search, &mut time);
+        }
+    }
+    search
+}
+
+fn _dfs_visit<G: IntoNeighbors>(graph: G, root: usize, search: &mut DepthFirstSearch, time: &mut usize) {
+    // iterative, every stack frame holds a vertex and the iterator of its remaining neighbors
+    let mut stack = vec![(root, graph.neighbors(root))];
+    search.discovery[root] = Some(*time);
+    *time += 1;
+    search.preorder.push(root);
+
+    while let Some((vertex, neighbors)) = stack.last_mut() {
+        let vertex = *vertex;
+        if let Some(neighbor) = neighbors.next() {
+            let kind = match (search.discovery[neighbor], search.finish[neighbor]) {
+                (None, _) => EdgeKind::Tree,
+                (Some(_), None) => EdgeKind::Back,
+                (Some(discovery), Some(_)) if discovery > search.discovery[vertex].unwrap() => EdgeKind::Forward,
+                _ => EdgeKind::Cross,
+            };
+            search.edges.push((vertex, neighbor, kind));
+            if kind == EdgeKind::Tree {
+                search.parents[neighbor] = Some(vertex);
+                search.discovery[neighbor] = Some(*time);
+                *time += 1;
+                search.preorder.push(neighbor);
+                stack.push((neighbor, graph.neighbors(neighbor)));
+            }
+        } else {
+            search.finish[vertex] = Some(*time);
+            *time += 1;
+            search.postorder.push(vertex);
+            stack.pop();
+        }
+    }
+}
+
+/// Return a cycle of the graph by a depth first search of the whole graph which stops at the first back edge,
+/// or `None` if the graph is acyclic
+///
+/// Unlike `dfs_all` the edges are not recorded, only a colour per node and the current path are kept.
+///
+pub fn find_cycle<G: IntoNeighbors>(graph: G) -> Option<Cycle> {
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    enum Colour {
+        White,
+        Grey,
+        Black,
+    }
+
+    let mut colours = vec![Colour::White; graph.node_bound()];
+    for root in _node_indices(&graph) {
+        if colours[root] != Colour::White {
+            continue;
+        }
+        // the stack holds the tree path from the root, the grey nodes
+        let mut stack = vec![(root, graph.neighbors(root))];
+        colours[root] = Colour::Grey;
+        while let Some((vertex, neighbors)) = stack.last_mut() {
+            let vertex = *vertex;
+            match neighbors.next().map(|neighbor| (neighbor, colours[neighbor])) {
+                Some((neighbor, Colour::White)) => {
+                    colours[neighbor] = Colour::Grey;
+                    stack.push((neighbor, graph.neighbors(neighbor)));
+                }
+                Some((neighbor, Colour::Grey)) => {
+                    // a back edge closes the cycle of the tree path from its target down to its source
+                    let start = stack.iter().position(|(node, _)| *node == neighbor).unwrap();
+                    return Some(Cycle::new(stack[start..].iter().map(|(node, _)| *node).collect()));
+                }
+                Some((_, Colour::Black)) => {}
+                None => {
+                    colours[vertex] = Colour::Black;
+                    stack.pop();
+                }
+            }
+        }
+    }
+    None
+}
+
+/// Return the topological order of the nodes by the Kahn algorithm, every node comes before its neighbors,
+/// or a cycle when the graph is not acyclic
+pub fn topological_sort<G: IntoNeighbors + NodeCount>(graph: G) -> Result<Vec<usize>, Cycle> {
+    let mut indegree = _indegrees(graph);
+    let mut queue: VecDeque<usize> = _node_indices(&graph).filter(|&node| indegree[node] == 0).collect();
+    let mut order = Vec::new();
+    while let Some(vertex) = queue.pop_front() {
+        order.push(vertex);
+        for neighbor in graph.neighbors(vertex) {
+            indegree[neighbor] -= 1;
+            if indegree[neighbor] == 0 {
+                queue.push_back(neighbor);
+            }
+        }
+    }
+    if order.len() < graph.node_count() {
+        return Err(_remaining_cycle(graph, &indegree));
+    }
+    Ok(order)
+}
+
+/// Return the topological order where the next node is always the one with the highest priority
+/// by the comparator among the nodes whose predecessors are all sorted, or a cycle when the graph is not acyclic
+pub fn topological_sort_by<G, F>(graph: G, comparator: F) -> Result<Vec<usize>, Cycle>
+where
+    G: IntoNeighbors + NodeCount,
+    F: Fn(&usize, &usize) -> bool,
+{
+    let mut indegree = _indegrees(graph);
+    let mut queue = PriorityQueue::new(comparator);
+    for node in _node_indices(&graph).filter(|&node| indegree[node] == 0) {
+        queue.push(node);
+    }
+    let mut order = Vec::new();
+    while let Some(vertex) = queue.pop() {
+        order.push(vertex);
+        for neighbor in graph.neighbors(vertex) {
+            indegree[neighbor] -= 1;
+            if indegree[neighbor] == 0 {
+                queue.push(neighbor);
+            }
+        }
+    }
+    if order.len() < graph.node_count() {
+        return Err(_remaining_cycle(graph, &indegree));
+    }
+    Ok(order)
+}
+
+// the indegree of every node, indexed by node id
+fn _indegrees<G: IntoNeighbors>(graph: G) -> Vec<usize> {
+    let mut indegree = vec![0; graph.node_bound()];
+    for vertex in _node_indices(&graph) {
+        for neighbor in graph.neighbors(vertex) {
+            indegree[neighbor] += 1;
+        }
+    }
+    indegree
+}
+
+// the nodes left with a positive indegree by the Kahn algorithm all have a remaining predecessor,
+// walking back the predecessors always ends up on a cycle
+fn _remaining_cycle<G: IntoNeighbors>(graph: G, indegree: &[usize]) -> Cycle {
+    let mut predecessors = vec![None; graph.node_bound()];
+    for vertex in _node_indices(&graph).filter(|&node| indegree[node] > 0) {
+        for neighbor in graph.neighbors(vertex) {
+            if indegree[neighbor] > 0 {
+                predecessors[neighbor] = Some(vertex);
+            }
+        }
+    }
+    let start = _node_indices(&graph).find(|&node| indegree[node] > 0).unwrap();
+    Cycle::from_predecessors(&predecessors, start)
+}
+
+/// Return the distances and the predecessor tree of the shortest paths from the source node
+/// by the Dijkstra algorithm, the weights must not be negative
+///
+/// A node only reached through paths whose distance overflows `W` has no distance,
+/// it is reported by `ShortestPaths::is_overflowed` instead of as unreachable.
+///
+/// # Panics
+///
+/// Panics if the source node is out of range, or if the weight of an edge reached from the source
+/// is negative or NaN.
+///
+pub fn dijkstra<G>(graph: G, source: usize) -> ShortestPaths<G::EdgeWeight>
+where
+    G: IntoEdges,
+    G::EdgeWeight: Weight,
+{
+    _dijkstra(graph, source).0
+}
+
+// the shortest paths and the order the nodes are settled in
+pub(crate) fn _dijkstra<G, W>(graph: G, source: usize) -> (ShortestPaths<W>, Vec<usize>)
+where
+    G: IntoEdges<EdgeWeight = W>,
+    W: Weight,
+{
+    let mut distances = vec![None; graph.node_bound()];
+    let mut predecessors = vec![None; graph.node_bound()];
+    let mut overflow = vec![false; graph.node_bound()];
+    let mut visit = Vec::new();
+    // the heap holds the tentative distance of every node, a node is settled once popped
+    let mut heap = IndexedPriorityQueue::new(|a: &W, b: &W| a < b);
+    distances[source] = Some(W::zero());
+    heap.push(source, W::zero());
+
+    while let Some((vertex, distance)) = heap.pop() {
+        visit.push(vertex);
+        for (neighbor_vertex, weight) in graph.edges(vertex) {
+            assert!(weight.is_valid() && weight >= W::zero(), "invalid edge weight: {:?}", weight);
+            // an overflowing distance is not followed, the neighbor is overflowed if it's never reached otherwise
+            let distance = match distance.checked_add(weight) {
+                Some(distance) => distance,
+                None => {
+                    overflow[neighbor_vertex] = true;
+                    continue;
+                }
+            };
+            if distances[neighbor_vertex].is_none_or(|current| distance < current) {
+                distances[neighbor_vertex] = Some(distance);
+                predecessors[neighbor_vertex] = Some(vertex);
+                heap.push(neighbor_vertex, distance);
+            }
+        }
+    }
+    let overflowed = dijkstra::_overflowed(&distances, overflow, |node| graph.neighbors(node));
+    (ShortestPaths::new(source, distances, predecessors, overflowed), visit)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CsrGraph, Graph};
+
+    // an implicit width x height grid, every cell has an edge to the cell on its right and below it,
+    // the cost of entering a cell is computed from its coordinates, no edge is ever stored
+    #[derive(Clone, Copy)]
+    struct Grid {
+        width: usize,
+        height: usize,
+    }
+
+    impl Grid {
+        fn cost(&self, node: usize) -> u64 {
+            (node % self.width * 7 + node / self.width * 3) as u64 % 5 + 1
+        }
+    }
+
+    impl GraphBase for Grid {
+        fn node_bound(&self) -> usize {
+            self.width * self.height
+        }
+    }
+
+    impl NodeCount for Grid {
+        fn node_count(&self) -> usize {
+            self.width * self.height
+        }
+    }
+
+    impl IntoNeighbors for Grid {
+        fn neighbors(self, node: usize) -> impl Iterator<Item = usize> {
+            let right = (node % self.width + 1 < self.width).then(|| node + 1);
+            let down = (node / self.width + 1 < self.height).then(|| node + self.width);
+            right.into_iter().chain(down)
+        }
+    }
+
+    impl IntoEdges for Grid {
+        type EdgeWeight = u64;
+
+        fn edges(self, node: usize) -> impl Iterator<Item = (usize, u64)> {
+            self.neighbors(node).map(move |neighbor| (neighbor, self.cost(neighbor)))
+        }
+    }
+
+    // the same grid copied into a Graph
+    fn _stored(grid: Grid) -> Graph<(), u64> {
+        let edges = (0..grid.node_bound())
+            .flat_map(|node| grid.edges(node).map(move |(neighbor, weight)| (node, neighbor, weight)))
+            .collect();
+        Graph::from_edges(vec![(); grid.node_bound()], edges)
+    }
+
+    #[test]
+    fn test_implicit_grid() {
+        let grid = Grid { width: 7, height: 5 };
+        let graph = _stored(grid);
+
+        let search = bfs(grid, 0);
+        assert_eq!(search, graph.bfs(0));
+        for node in 0..grid.node_bound() {
+            assert_eq!(search.level(node), Some(node % 7 + node / 7));
+        }
+        assert_eq!(dfs(grid, 0), graph.dfs(0));
+        assert_eq!(dfs_all(grid), graph.dfs_all());
+        assert_eq!(find_cycle(grid), None);
+
+        let order = topological_sort(grid).unwrap();
+        assert_eq!(order, graph.topological_sort().unwrap());
+        let mut position = vec![0; order.len()];
+        for (index, &node) in order.iter().enumerate() {
+            position[node] = index;
+        }
+        for node in 0..grid.node_bound() {
+            assert!(grid.neighbors(node).all(|neighbor| position[node] < position[neighbor]));
+        }
+        let order = topological_sort_by(grid, |a, b| a > b).unwrap();
+        assert_eq!(order, graph.topological_sort_by(|a, b| a > b).unwrap());
+
+        let paths = dijkstra(grid, 0);
+        println!("grid distance: {:?}", paths.distance(34));
+        assert_eq!(paths, dijkstra(&graph, 0));
+        assert_eq!(paths, dijkstra(&CsrGraph::from(&graph), 0));
+        // on a right/down grid the distance is the best sum of the costs of the entered cells
+        let mut best = vec![0; grid.node_bound()];
+        for node in 1..grid.node_bound() {
+            let (x, y) = (node % 7, node / 7);
+            let left = (x > 0).then(|| best[node - 1]);
+            let up = (y > 0).then(|| best[node - 7]);
+            best[node] = left.into_iter().chain(up).min().unwrap() + grid.cost(node);
+        }
+        for (node, &distance) in best.iter().enumerate() {
+            assert_eq!(paths.distance(node), Some(distance));
+        }
+    }
+
+    #[test]
+    fn test_cycle_in_implicit_graph() {
+        // the ring 0 -> 1 -> .. -> 5 -> 0
+        #[derive(Clone, Copy)]
+        struct Ring(usize);
+
+        impl GraphBase for Ring {
+            fn node_bound(&self) -> usize {
+                self.0
+            }
+        }
+
+        impl NodeCount for Ring {
+            fn node_count(&self) -> usize {
+                self.0
+            }
+        }
+
+        impl IntoNeighbors for Ring {
+            fn neighbors(self, node: usize) -> impl Iterator<Item = usize> {
+                std::iter::once((node + 1) % self.0)
+            }
+        }
+
+        let cycle = topological_sort(Ring(6)).unwrap_err();
+        assert_eq!(cycle.nodes().len(), 6);
+        assert_eq!(find_cycle(Ring(6)), Some(cycle));
+        assert_eq!(bfs(Ring(6), 3).order(), &[3, 4, 5, 0, 1, 2]);
+    }
+
+    #[test]
+    fn test_reference_of_graph() {
+        let graph = Graph::new(4, vec![(1, 0), (2, 1), (3, 1)]);
+        assert_eq!(GraphBase::node_bound(&&graph), 4);
+        assert_eq!(NodeCount::node_count(&&graph), 4);
+        assert_eq!(IntoNeighbors::neighbors(&graph, 1).collect::<Vec<_>>(), vec![2, 3]);
+        assert_eq!(bfs(&graph, 0), graph.bfs(0));
+    }
+}
@@ -0,0 +1,195 @@
+//! Weight
+//!
+//! This trait describes the edge weight of the weighted graph algorithms,
+//! it is implemented for all the integer and float primitives.
+//!
+//! # Example
+//!
+//! ```
+//! use flex_algo::Weight;
+//!
+//! assert_eq!(u8::zero(), 0);
+//! assert_eq!(Weight::checked_add(200u8, 100u8), None);
+//! assert_eq!(Weight::checked_add(1.5f64, 2.0), Some(3.5));
+//! assert_eq!(Weight::checked_sub(3u8, 5u8), None);
+//! assert_eq!(Weight::checked_mul(3u8, 5u8), Some(15));
+//! assert!(!f64::NAN.is_valid());
+//! assert_eq!(Weight::checked_add((-100i8).widen(), (-100i8).widen()), Some(-200));
+//! assert_eq!(i8::narrow(-200), None);
+//! ```
+
+use std::fmt::Debug;
+
+/// Weight
+///
+/// An edge weight with a zero value, an addition, a subtraction and a multiplication with overflow checking and an ordering.
+///
+/// Overflow: `checked_add`, `checked_sub` and `checked_mul` return `None` when the result overflows an integer or is not finite for a float,
+/// the shortest path algorithms then don't follow the path going through that edge instead of wrapping around,
+/// and a node only reached through such paths is reported by `ShortestPaths::is_overflowed` rather than as unreachable.
+///
+/// Wide: the sums of many weights, e.g. the distances and the potentials, are computed in the wider type `Wide`
+/// where a path of narrow weights can't overflow, and only the results are narrowed back to the weight.
+/// The 128 bit integers and `f64` are their own wide type.
+///
+/// NaN: `is_valid` returns false for NaN, such weights are rejected when the graph is created.
+///
+pub trait Weight: Copy + PartialOrd + Debug {
+    /// The wider type the sums of weights are computed in
+    type Wide: Weight;
+
+    /// Return the weight in the wide type, exactly
+    fn widen(self) -> Self::Wide;
+
+    /// Return the wide value as a weight, or `None` if it doesn't fit
+    fn narrow(wide: Self::Wide) -> Option<Self>;
+
+    /// Return the zero weight, the distance from a node to itself
+    fn zero() -> Self;
+
+    /// Return the sum of the two weights, or `None` on overflow
+    fn checked_add(self, other: Self) -> Option<Self>;
+
+    /// Return the difference of the two weights, or `None` on overflow
+    fn checked_sub(self, other: Self) -> Option<Self>;
+
+    /// Return the product of the two weights, or `None` on overflow, e.g. a flow times its unit cost
+    fn checked_mul(self, other: Self) -> Option<Self>;
+
+    /// Return false if the weight can't be ordered, e.g. NaN
+    fn is_valid(self) -> bool {
+        true
+    }
+}
+
+macro_rules! impl_integer_weight {
+    ($($t:ty => $wide:ty),*) => {
+        $(
+            impl Weight for $t {
+                type Wide = $wide;
+
+                fn widen(self) -> $wide {
+                    self as $wide
+                }
+
+                fn narrow(wide: $wide) -> Option<Self> {
+                    <$t>::try_from(wide).ok()
+                }
+
+                fn zero() -> Self {
+                    0
+                }
+
+                fn checked_add(self, other: Self) -> Option<Self> {
+                    <$t>::checked_add(self, other)
+                }
+
+                fn checked_sub(self, other: Self) -> Option<Self> {
+                    <$t>::checked_sub(self, other)
+                }
+
+                fn checked_mul(self, other: Self) -> Option<Self> {
+                    <$t>::checked_mul(self, other)
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_float_weight {
+    ($($t:ty),*) => {
+        $(
+            impl Weight for $t {
+                type Wide = f64;
+
+                fn widen(self) -> f64 {
+                    self as f64
+                }
+
+                fn narrow(wide: f64) -> Option<Self> {
+                    let weight = wide as $t;
+                    if weight.is_finite() || !wide.is_finite() {
+                        return Some(weight);
+                    }
+                    None
+                }
+
+                fn zero() -> Self {
+                    0.0
+                }
+
+                fn checked_add(self, other: Self) -> Option<Self> {
+                    let sum = self + other;
+                    if sum.is_finite() {
+                        return Some(sum);
+                    }
+                    None
+                }
+
+                fn checked_sub(self, other: Self) -> Option<Self> {
+                    let difference = self - other;
+                    if difference.is_finite() {
+                        return Some(difference);
+                    }
+                    None
+                }
+
+                fn checked_mul(self, other: Self) -> Option<Self> {
+                    let product = self * other;
+                    if product.is_finite() {
+                        return Some(product);
+                    }
+                    None
+                }
+
+                fn is_valid(self) -> bool {
+                    !self.is_nan()
+                }
+            }
+        )*
+    };
+}
+
+impl_integer_weight!(
+    u8 => i64, u16 => i64, u32 => i128, u64 => i128, u128 => u128, usize => i128,
+    i8 => i64, i16 => i64, i32 => i128, i64 => i128, i128 => i128, isize => i128
+);
+impl_float_weight!(f32, f64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_integer_weight() {
+        assert_eq!(<i64 as Weight>::zero(), 0);
+        assert_eq!(Weight::checked_add(3usize, 4), Some(7));
+        assert_eq!(Weight::checked_add(usize::MAX, 1), None);
+        assert_eq!(Weight::checked_add(i8::MIN, -1), None);
+        assert_eq!(Weight::checked_sub(2u16, 3), None);
+        assert_eq!(Weight::checked_sub(2i16, 3), Some(-1));
+        assert_eq!(Weight::checked_mul(u64::MAX, 2), None);
+        assert_eq!(Weight::checked_mul(-4i32, 3), Some(-12));
+        assert!(5u32.is_valid());
+        assert_eq!(u64::MAX.widen() * 3, 3 * u64::MAX as i128);
+        assert_eq!(u8::narrow(-1), None);
+        assert_eq!(i16::narrow(-300), Some(-300));
+        assert_eq!(u128::narrow(u128::MAX), Some(u128::MAX));
+    }
+
+    #[test]
+    fn test_float_weight() {
+        assert_eq!(<f32 as Weight>::zero(), 0.0);
+        assert_eq!(Weight::checked_add(0.5f32, 0.25), Some(0.75));
+        assert_eq!(Weight::checked_add(f64::MAX, f64::MAX), None);
+        assert_eq!(Weight::checked_sub(0.5f64, 2.0), Some(-1.5));
+        assert_eq!(Weight::checked_sub(f32::MIN, f32::MAX), None);
+        assert_eq!(Weight::checked_mul(1.5f64, 4.0), Some(6.0));
+        assert_eq!(Weight::checked_mul(f64::MAX, 2.0), None);
+        assert!(1.0f64.is_valid());
+        assert!(!f32::NAN.is_valid());
+        assert_eq!(f32::MAX.widen() * 2.0, f32::MAX as f64 * 2.0);
+        assert_eq!(f32::narrow(f32::MAX as f64 * 2.0), None);
+        assert_eq!(f32::narrow(0.5), Some(0.5));
+    }
+}